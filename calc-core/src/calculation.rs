@@ -0,0 +1,2379 @@
+use crate::{
+    complex::{self, Complex},
+    duration::{Duration, TimeUnit},
+    numeric_value::{precise, NumericValue, PRECISION},
+};
+use dashu::rational::RBig;
+use dashu_float::DBig;
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Range, Sub},
+    str::FromStr,
+};
+
+/// An operation between two operands. `Equals` only marks the end of a
+/// finished calculation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[non_exhaustive]
+pub enum Operation {
+    Division,
+    Multiplication,
+    Subtraction,
+    Addition,
+    Equals,
+}
+
+/// The value of an operand. `Undefined` is the result of invalid operations
+/// such as division by zero.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum OperandValue {
+    Number(NumericValue),
+    Complex(Complex),
+    Duration(Duration),
+    Undefined,
+}
+
+/// An operand of the entry line and the operation typed after it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Operand {
+    operation: Option<Operation>,
+    value: OperandValue,
+}
+
+/// The calculator's entry line: the operands typed so far, and those of the last
+/// finished calculation shown above it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calculation {
+    past_operands: Vec<Operand>,
+    operands: Vec<Operand>,
+}
+
+impl Operand {
+    /// The operation typed after this operand, if any.
+    pub fn operation(&self) -> Option<&Operation> {
+        self.operation.as_ref()
+    }
+
+    /// The value of this operand.
+    pub fn value(&self) -> &OperandValue {
+        &self.value
+    }
+}
+
+impl Calculation {
+    /// The operands of the entry line.
+    pub fn operands(&self) -> &[Operand] {
+        &self.operands
+    }
+
+    /// Operands of the last completed calculation, shown above the entry.
+    pub fn past_operands(&self) -> &[Operand] {
+        &self.past_operands
+    }
+
+    /// Works out the entry line, like pressing `=`. Pressing it again on a result
+    /// repeats the last operation.
+    pub fn calculate(&mut self) {
+        if self.operands.len() == 1 {
+            self.repeat_constant();
+            return;
+        }
+
+        if self.operands.is_empty() {
+            return;
+        }
+
+        self.past_operands = self.operands.clone();
+
+        let (value, _) = calculate(&self.operands);
+
+        self.operands = vec![Operand {
+            operation: None,
+            value,
+        }]
+    }
+
+    /// The result `calculate` would give, worked out on a copy. None while there
+    /// is nothing to combine, an operation is pending or the result is undefined.
+    pub fn preview(&self) -> Option<OperandValue> {
+        if self.operands.len() < 2 || self.has_pending_operation() {
+            return None;
+        }
+
+        let mut preview = self.clone();
+        preview.calculate();
+
+        Some(preview.current_operand_value()).filter(|value| *value != OperandValue::Undefined)
+    }
+
+    /// The operation and constant operand that pressing `=` again would repeat
+    /// on the result shown.
+    pub fn repeated_operation(&self) -> Option<(Operation, OperandValue)> {
+        match self.operands.as_slice() {
+            [operand] if operand.operation.is_none() => self.constant(),
+            _ => None,
+        }
+    }
+
+    fn repeat_constant(&mut self) {
+        let Some((operation, constant)) = self.repeated_operation() else {
+            return;
+        };
+
+        let current = self.operands[0].value.clone();
+        let (left, right) = match operation {
+            Operation::Multiplication => (constant, current),
+            _ => (current, constant),
+        };
+
+        self.operands = vec![
+            Operand {
+                operation: Some(operation),
+                value: left,
+            },
+            Operand {
+                operation: None,
+                value: right,
+            },
+        ];
+
+        self.calculate();
+    }
+
+    fn constant(&self) -> Option<(Operation, OperandValue)> {
+        let (last, rest) = self.past_operands.split_last()?;
+        let operation = rest.last()?.operation.clone()?;
+
+        match operation {
+            Operation::Equals => None,
+            Operation::Multiplication => Some((operation, calculate(rest).0)),
+            _ => Some((operation, last.value.clone())),
+        }
+    }
+
+    /// Clears the entry line, keeping the last calculation as a finished one.
+    pub fn start_over(&mut self) {
+        if let Some(last) = self.past_operands.last_mut() {
+            last.operation = Some(Operation::Equals);
+        }
+
+        self.operands = vec![Operand::default()];
+    }
+
+    fn is_showing_result(&self) -> bool {
+        self.past_operands
+            .last()
+            .is_some_and(|operand| operand.operation.is_none())
+    }
+
+    /// Whether the entry line holds no input of its own: nothing typed yet, or a
+    /// result or undefined value that typing replaces.
+    pub fn is_empty(&self) -> bool {
+        if self.operands.is_empty() || self.is_showing_result() {
+            return true;
+        }
+
+        if self.operands.len() > 1 {
+            return false;
+        }
+
+        if let Some(operand) = self.operands.first() {
+            let no_operation = operand.operation.is_none();
+            let equals_zero = operand
+                .value
+                .eq(&OperandValue::Number(NumericValue::default()));
+            let is_undefined = operand.value.eq(&OperandValue::Undefined);
+
+            return (no_operation && equals_zero) || is_undefined;
+        }
+
+        false
+    }
+
+    /// The entry line as displayed, such as `2+3⨉4`.
+    pub fn current_operation_string(&self) -> String {
+        let mut str = String::new();
+
+        for operand in &self.operands {
+            str.insert_str(str.len(), &operand.value.to_string());
+
+            if let Some(operation) = &operand.operation {
+                str.insert_str(str.len(), &operation.to_string());
+            }
+        }
+
+        str
+    }
+
+    /// The last finished calculation as displayed above the entry line.
+    pub fn past_operations_string(&self) -> String {
+        let mut str = String::new();
+
+        for operand in &self.past_operands {
+            str.insert_str(str.len(), &operand.value.to_string());
+
+            if let Some(operation) = &operand.operation {
+                str.insert_str(str.len(), &operation.to_string());
+            }
+        }
+
+        str
+    }
+
+    /// The last finished calculation with the operations spelled out for screen
+    /// readers.
+    pub fn past_operations_spoken(&self) -> String {
+        let mut str = String::new();
+
+        for operand in &self.past_operands {
+            str.insert_str(str.len(), &operand.value.to_string());
+
+            if let Some(operation) = &operand.operation {
+                str.insert_str(str.len(), &format!(" {} ", operation.spoken_name()));
+            }
+        }
+
+        str
+    }
+
+    /// The approximate memory used by the calculation, in bytes, including the
+    /// digits of its numbers, which grow without bound for exact values.
+    pub fn footprint(&self) -> usize {
+        let operands = self.past_operands.iter().chain(&self.operands);
+
+        size_of::<Self>()
+            + operands
+                .map(|operand| size_of::<Operand>() + operand.value.heap_size())
+                .sum::<usize>()
+    }
+
+    /// Whether the entry line ends with an operation still waiting for its operand.
+    pub fn has_pending_operation(&self) -> bool {
+        self.operands
+            .last()
+            .is_some_and(|operand| operand.operation.is_some())
+    }
+
+    /// The last operand, or zero when it is not a plain number.
+    pub fn current_value(&self) -> NumericValue {
+        match self.operands.last() {
+            Some(Operand {
+                value: OperandValue::Number(val),
+                ..
+            }) => val.clone(),
+            _ => NumericValue::default(),
+        }
+    }
+
+    /// The value of the last operand.
+    pub fn current_operand_value(&self) -> OperandValue {
+        self.operands
+            .last()
+            .map(|operand| operand.value.clone())
+            .unwrap_or_default()
+    }
+
+    /// Replaces the last operand with a number, see `replace_current_operand`.
+    pub fn replace_current_value(&mut self, value: NumericValue) {
+        self.replace_current_operand(OperandValue::Number(value));
+    }
+
+    /// Replaces the last operand, or adds one after a pending operation.
+    pub fn replace_current_operand(&mut self, value: OperandValue) {
+        match self.operands.last_mut() {
+            Some(operand) if operand.operation.is_none() => {
+                operand.value = value;
+            }
+            _ => self.operands.push(Operand {
+                operation: None,
+                value,
+            }),
+        }
+
+        if !self.past_operands.is_empty() {
+            self.past_operands = vec![];
+        }
+    }
+
+    /// Types a digit from 0 to 9 into the entry line.
+    pub fn append_number(&mut self, num: usize) {
+        let current_operand = self.operands.last_mut();
+
+        if let Some(&mut ref mut operand) = current_operand {
+            match operand.value.clone() {
+                OperandValue::Number(val) => {
+                    if operand.operation.is_some() {
+                        let new_val = DBig::from(num);
+
+                        self.operands.push(Operand {
+                            value: OperandValue::Number(NumericValue::new(new_val)),
+                            operation: None,
+                        });
+
+                        return;
+                    }
+
+                    if let Some(fraction) = val.with_fraction_digit(num) {
+                        operand.value = OperandValue::Number(fraction);
+
+                        return;
+                    }
+
+                    if val.has_comma() {
+                        let stringified = val.val().to_string();
+
+                        let appended = format!("{}.{}", stringified, num);
+
+                        operand.value = OperandValue::Number(NumericValue::new(
+                            DBig::from_str(&appended).unwrap(),
+                        ));
+
+                        return;
+                    }
+
+                    let (trunc, fract) = val.val().clone().split_at_point();
+                    let fract_precision = fract.precision();
+                    let trunc_precision = trunc.precision();
+                    let precision = 1 + trunc_precision + fract_precision;
+
+                    let zero = dbig!(0);
+                    let (trunc, fract) = if fract.gt(&zero) {
+                        let stringified = fract.to_string();
+
+                        let appended = format!("{}{}", stringified, num);
+
+                        (trunc.to_string(), appended)
+                    } else {
+                        let stringified = trunc.to_string();
+
+                        let appended = format!("{}{}", stringified, num);
+
+                        (appended, fract.to_string())
+                    };
+
+                    let trunc = DBig::from_str(&trunc).unwrap();
+                    let fract = DBig::from_str(&fract).unwrap();
+
+                    operand.value = OperandValue::Number(NumericValue::new(
+                        trunc
+                            .with_precision(precision)
+                            .value()
+                            .add(fract)
+                            .with_precision(precision)
+                            .value(),
+                    ));
+                }
+                OperandValue::Complex(_) => {
+                    if operand.operation.is_some() {
+                        self.operands.push(Operand {
+                            value: OperandValue::Number(NumericValue::new(DBig::from(num))),
+                            operation: None,
+                        });
+                    }
+                }
+                OperandValue::Duration(val) => {
+                    if operand.operation.is_some() {
+                        self.operands.push(Operand {
+                            value: OperandValue::Number(NumericValue::new(DBig::from(num))),
+                            operation: None,
+                        });
+
+                        return;
+                    }
+
+                    operand.value = OperandValue::Duration(val.with_digit(num));
+                }
+                // Typing after an undefined result starts a new calculation.
+                OperandValue::Undefined => {
+                    self.start_over();
+                    self.append_number(num);
+                }
+            }
+        } else {
+            self.operands.push(Operand {
+                value: OperandValue::Number(NumericValue::new(DBig::from(num))),
+                operation: None,
+            })
+        }
+    }
+
+    /// Types the decimal separator into the entry line.
+    pub fn add_comma(&mut self) {
+        let current_operand = self.operands.last_mut();
+
+        if let Some(&mut ref mut operand) = current_operand {
+            if operand.operation.is_some() {
+                self.operands.push(Operand {
+                    value: OperandValue::Number(NumericValue::new_with_comma(DBig::from(0))),
+                    operation: None,
+                });
+                return;
+            }
+
+            match operand.value.clone() {
+                OperandValue::Number(val) => {
+                    operand.value =
+                        OperandValue::Number(NumericValue::new_with_comma(val.val().clone()))
+                }
+                OperandValue::Complex(_) => {}
+                OperandValue::Duration(val) => {
+                    operand.value = OperandValue::Duration(val.with_comma())
+                }
+                OperandValue::Undefined => {
+                    self.start_over();
+                    self.add_comma();
+                }
+            }
+        } else {
+            self.operands.push(Operand {
+                value: OperandValue::Number(NumericValue::new_with_comma(DBig::from(0))),
+                operation: None,
+            })
+        }
+    }
+
+    /// Starts the denominator of an exact fraction, or of the fraction part of a
+    /// mixed number.
+    pub fn add_fraction_bar(&mut self) {
+        let Some(operand) = self.operands.last_mut() else {
+            return;
+        };
+
+        if operand.operation.is_some() {
+            return;
+        }
+
+        if let OperandValue::Number(val) = &operand.value {
+            if let Some(fraction) = val.with_fraction_bar() {
+                operand.value = OperandValue::Number(fraction);
+            }
+        }
+    }
+
+    /// Turns the last operand into a duration, or adds the next unit to one.
+    pub fn append_time_unit(&mut self, unit: TimeUnit) {
+        let Some(operand) = self.operands.last_mut() else {
+            return;
+        };
+
+        if operand.operation.is_some() {
+            return;
+        }
+
+        let duration = match &operand.value {
+            OperandValue::Number(val) => Some(Duration::new(val.val(), unit)),
+            OperandValue::Duration(val) => val.with_unit(unit),
+            _ => None,
+        };
+
+        if let Some(duration) = duration {
+            operand.value = OperandValue::Duration(duration);
+        }
+    }
+
+    /// Converts plain numbers in the entry line to exact fractions.
+    pub fn make_exact(&mut self) {
+        for operand in self.operands.iter_mut() {
+            if let OperandValue::Number(val) = &operand.value {
+                if let Some(exact) = val.to_exact().filter(|_| !val.is_exact()) {
+                    operand.value = OperandValue::Number(NumericValue::exact(exact));
+                }
+            }
+        }
+    }
+
+    /// Multiplies the last operand by i.
+    pub fn append_imaginary(&mut self) {
+        let Some(operand) = self.operands.last_mut() else {
+            return;
+        };
+
+        if operand.operation.is_some() {
+            self.operands.push(Operand {
+                operation: None,
+                value: OperandValue::Complex(Complex::new(DBig::ZERO, DBig::ONE)),
+            });
+            return;
+        }
+
+        let i = Complex::new(DBig::ZERO, DBig::ONE);
+
+        operand.value = match &operand.value {
+            OperandValue::Number(val) if *val == NumericValue::default() => {
+                OperandValue::Complex(i)
+            }
+            OperandValue::Number(val) => into_operand(Complex::from(val.val()).mul(&i)),
+            OperandValue::Complex(val) => into_operand(val.mul(&i)),
+            OperandValue::Duration(_) | OperandValue::Undefined => return,
+        };
+    }
+
+    /// Replaces the last operand with its square root, complex for negative numbers.
+    pub fn square_root(&mut self) {
+        let Some(operand) = self.operands.last_mut() else {
+            return;
+        };
+
+        if operand.operation.is_some() {
+            return;
+        }
+
+        operand.value = match &operand.value {
+            OperandValue::Number(val) if *val.val() < DBig::ZERO => {
+                OperandValue::Complex(Complex::new(DBig::ZERO, complex::sqrt(&-val.val().clone())))
+            }
+            OperandValue::Number(val) => {
+                OperandValue::Number(NumericValue::new(complex::sqrt(val.val())))
+            }
+            OperandValue::Complex(val) => into_operand(val.sqrt()),
+            OperandValue::Duration(_) | OperandValue::Undefined => return,
+        };
+    }
+
+    /// Switches a complex operand between rectangular and polar form.
+    pub fn toggle_polar(&mut self) {
+        if let Some(Operand {
+            value: OperandValue::Complex(val),
+            ..
+        }) = self.operands.last_mut()
+        {
+            val.toggle_polar();
+        }
+    }
+
+    /// Switches an exact operand between fraction and decimal form.
+    pub fn toggle_fraction(&mut self) {
+        if let Some(Operand {
+            value: OperandValue::Number(val),
+            ..
+        }) = self.operands.last_mut()
+        {
+            val.toggle_fraction();
+        }
+    }
+
+    /// Adds an operation after the last operand, replacing a pending one.
+    /// `Equals` is ignored; use `calculate` instead.
+    pub fn append_operation(&mut self, op: Operation) {
+        if op == Operation::Equals {
+            return;
+        }
+
+        let current_operand = self.operands.last_mut();
+        if let Some(&mut ref mut operand) = current_operand {
+            operand.operation = Some(op)
+        }
+
+        if !self.past_operands.is_empty() {
+            self.past_operands = vec![];
+        }
+    }
+
+    /// Removes the last typed character or operation, like backspace.
+    pub fn remove_last(&mut self) {
+        let operands_len = self.operands.len();
+        let current_operand = self.operands.last_mut();
+
+        if let Some(&mut ref mut operand) = current_operand {
+            if operand.operation.is_some() {
+                operand.operation = None;
+
+                return;
+            }
+
+            let val = operand.value.clone();
+
+            match val {
+                OperandValue::Number(ref num) if num.is_exact() => {
+                    operand.value = OperandValue::default();
+                }
+                OperandValue::Complex(_) => {
+                    operand.value = OperandValue::default();
+                }
+                OperandValue::Duration(ref duration) => {
+                    operand.value = duration
+                        .without_last()
+                        .map(OperandValue::Duration)
+                        .unwrap_or_default();
+                }
+                OperandValue::Number(ref num) => {
+                    let was_float = num.is_float();
+
+                    if num.eq(&NumericValue::new(dbig!(0))) {
+                        if operands_len > 1 {
+                            self.operands.pop();
+                        }
+
+                        return;
+                    };
+
+                    let mut new_value = format!("{}", num);
+                    new_value.pop();
+
+                    if new_value.is_empty() && operands_len > 1 {
+                        self.operands.pop();
+                        return;
+                    }
+
+                    let new_value = if new_value.is_empty() {
+                        "0".to_string()
+                    } else {
+                        new_value
+                    };
+
+                    let new_value = DBig::from_str(&new_value).unwrap();
+                    let num_value = NumericValue::new(new_value.clone());
+                    let is_float = num_value.is_float();
+
+                    if was_float && !is_float {
+                        operand.value =
+                            OperandValue::Number(NumericValue::new_with_comma(new_value));
+                    } else {
+                        operand.value = OperandValue::Number(num_value);
+                    }
+                }
+                OperandValue::Undefined => {
+                    operand.value = OperandValue::default();
+                }
+            }
+        };
+    }
+
+    /// Only plain numbers and durations can be edited as text; fractions and
+    /// complex numbers display in forms that do not parse back.
+    pub fn is_editable(&self) -> bool {
+        self.operands.iter().all(|operand| match &operand.value {
+            OperandValue::Number(val) => !val.is_exact(),
+            OperandValue::Duration(_) => true,
+            _ => false,
+        })
+    }
+
+    /// Replaces the characters in `range` of the entry line with `text`,
+    /// returning None when the result is not a valid entry.
+    pub fn edit(&self, range: Range<usize>, text: &str) -> Option<Calculation> {
+        if !self.is_editable() {
+            return None;
+        }
+
+        let current: Vec<char> = self.current_operation_string().chars().collect();
+        let range = range.start.min(current.len())..range.end.min(current.len());
+
+        let mut edited: String = current[..range.start].iter().collect();
+        edited.push_str(text);
+        edited.extend(&current[range.end..]);
+
+        Some(Calculation {
+            past_operands: vec![],
+            operands: parse_operands(&edited)?,
+        })
+    }
+
+    /// Parses an entry line as the keypad would build it, such as `2+3⨉4` or
+    /// `1h 25m+47m`. `*` and `/` stand in for ⨉ and ÷, spaces are ignored and
+    /// a trailing `=` calculates.
+    pub fn parse(text: &str) -> Option<Calculation> {
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                '*' => '⨉',
+                '/' => '÷',
+                c => c,
+            })
+            .collect();
+        let (text, equals) = match text.strip_suffix(['=', '＝']) {
+            Some(text) => (text, true),
+            None => (text.as_str(), false),
+        };
+
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: parse_operands(text)?,
+        };
+        if equals {
+            calculation.calculate();
+        }
+
+        Some(calculation)
+    }
+}
+
+fn calculate(operands: &[Operand]) -> (OperandValue, Option<Operation>) {
+    operands.iter().fold(
+        (OperandValue::default(), None),
+        |(acc, operation), operand| match (acc, operand.value.clone()) {
+            (OperandValue::Number(acc), OperandValue::Number(val)) => {
+                if let Some(op) = operation
+                    .clone()
+                    .filter(|_| acc.is_exact() || val.is_exact())
+                {
+                    if let (Some(acc), Some(val)) = (acc.to_exact(), val.to_exact()) {
+                        return (calculate_exact(op, acc, val), operand.operation.clone());
+                    }
+                }
+
+                if let Some(op) = operation {
+                    let new_value: OperandValue = match op {
+                        Operation::Addition => OperandValue::Number(NumericValue::new(
+                            precise(acc.val())
+                                .add(val.val())
+                                .with_precision(PRECISION)
+                                .value(),
+                        )),
+                        Operation::Subtraction => OperandValue::Number(NumericValue::new(
+                            precise(acc.val())
+                                .sub(val.val())
+                                .with_precision(PRECISION)
+                                .value(),
+                        )),
+                        Operation::Multiplication => OperandValue::Number(NumericValue::new(
+                            precise(acc.val())
+                                .mul(val.val())
+                                .with_precision(PRECISION)
+                                .value(),
+                        )),
+                        Operation::Division => {
+                            if val.val().eq(&dbig!(0)) {
+                                OperandValue::Undefined
+                            } else {
+                                OperandValue::Number(NumericValue::new(
+                                    precise(acc.val())
+                                        .div(val.val())
+                                        .with_precision(PRECISION)
+                                        .value(),
+                                ))
+                            }
+                        }
+                        Operation::Equals => OperandValue::Undefined,
+                    };
+
+                    return (new_value, operand.operation.clone());
+                };
+
+                (OperandValue::Number(val), operand.operation.clone())
+            }
+            (acc @ OperandValue::Complex(_), val @ OperandValue::Number(_))
+            | (acc @ OperandValue::Number(_), val @ OperandValue::Complex(_))
+            | (acc @ OperandValue::Complex(_), val @ OperandValue::Complex(_)) => {
+                let (Some(acc), Some(complex_val)) = (to_complex(&acc), to_complex(&val)) else {
+                    return (OperandValue::Undefined, None);
+                };
+
+                let new_value = match operation {
+                    Some(Operation::Addition) => into_operand(acc.add(&complex_val)),
+                    Some(Operation::Subtraction) => into_operand(acc.sub(&complex_val)),
+                    Some(Operation::Multiplication) => into_operand(acc.mul(&complex_val)),
+                    Some(Operation::Division) => acc
+                        .div(&complex_val)
+                        .map(into_operand)
+                        .unwrap_or(OperandValue::Undefined),
+                    Some(Operation::Equals) => OperandValue::Undefined,
+                    None => val,
+                };
+
+                (new_value, operand.operation.clone())
+            }
+            (acc @ OperandValue::Duration(_), val @ OperandValue::Number(_))
+            | (acc @ OperandValue::Number(_), val @ OperandValue::Duration(_))
+            | (acc @ OperandValue::Duration(_), val @ OperandValue::Duration(_)) => (
+                calculate_duration(operation, acc, val),
+                operand.operation.clone(),
+            ),
+            _ => (OperandValue::Undefined, None),
+        },
+    )
+}
+
+fn parse_operands(text: &str) -> Option<Vec<Operand>> {
+    let mut operands = vec![];
+    let mut number = String::new();
+
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        let operation = match c {
+            '0'..='9' | '.' | 'd' | 'h' | 'm' | 's' => None,
+            '-' if number.is_empty() => None,
+            '+' => Some(Operation::Addition),
+            '-' => Some(Operation::Subtraction),
+            '⨉' => Some(Operation::Multiplication),
+            '÷' => Some(Operation::Division),
+            _ => return None,
+        };
+
+        match operation {
+            Some(operation) => operands.push(Operand {
+                operation: Some(operation),
+                value: parse_number(&std::mem::take(&mut number))?,
+            }),
+            None => number.push(c),
+        }
+    }
+
+    // A trailing operation is a pending one rather than an empty operand.
+    if number.is_empty() && !operands.is_empty() {
+        return Some(operands);
+    }
+
+    operands.push(Operand {
+        operation: None,
+        value: parse_number(&number)?,
+    });
+
+    Some(operands)
+}
+
+fn parse_number(text: &str) -> Option<OperandValue> {
+    if text.contains(['d', 'h', 'm', 's']) {
+        return Duration::parse(text).map(OperandValue::Duration);
+    }
+
+    let (digits, comma) = match text.strip_suffix('.') {
+        Some(digits) => (digits, true),
+        None => (text, false),
+    };
+
+    let value = match digits {
+        "" => DBig::ZERO,
+        "-" => return None,
+        digits => DBig::from_str(digits).ok()?,
+    };
+
+    Some(OperandValue::Number(match comma {
+        true => NumericValue::new_with_comma(value),
+        false => NumericValue::new(value),
+    }))
+}
+
+fn to_complex(value: &OperandValue) -> Option<Complex> {
+    match value {
+        OperandValue::Number(val) => Some(Complex::from(val.val())),
+        OperandValue::Complex(val) => Some(val.clone()),
+        OperandValue::Duration(_) | OperandValue::Undefined => None,
+    }
+}
+
+fn calculate_duration(
+    operation: Option<Operation>,
+    acc: OperandValue,
+    val: OperandValue,
+) -> OperandValue {
+    let result = match (operation, &acc, &val) {
+        (None, _, _) => return val,
+        (Some(Operation::Addition), OperandValue::Duration(acc), OperandValue::Duration(val)) => {
+            Some(OperandValue::Duration(acc.add(val)))
+        }
+        (
+            Some(Operation::Subtraction),
+            OperandValue::Duration(acc),
+            OperandValue::Duration(val),
+        ) => Some(OperandValue::Duration(acc.sub(val))),
+        (Some(Operation::Division), OperandValue::Duration(acc), OperandValue::Duration(val)) => {
+            acc.ratio(val)
+                .map(|ratio| OperandValue::Number(NumericValue::new(ratio)))
+        }
+        (
+            Some(Operation::Multiplication),
+            OperandValue::Duration(acc),
+            OperandValue::Number(val),
+        )
+        | (
+            Some(Operation::Multiplication),
+            OperandValue::Number(val),
+            OperandValue::Duration(acc),
+        ) => Some(OperandValue::Duration(acc.mul(val.val()))),
+        (Some(Operation::Division), OperandValue::Duration(acc), OperandValue::Number(val)) => {
+            acc.div(val.val()).map(OperandValue::Duration)
+        }
+        _ => None,
+    };
+
+    result.unwrap_or(OperandValue::Undefined)
+}
+
+/// Turns a complex result into an operand, as a plain number when it is real.
+pub fn into_operand(value: Complex) -> OperandValue {
+    if value.is_real() {
+        OperandValue::Number(NumericValue::new(value.re().clone()))
+    } else {
+        OperandValue::Complex(value)
+    }
+}
+
+fn calculate_exact(op: Operation, acc: RBig, val: RBig) -> OperandValue {
+    let value = match op {
+        Operation::Addition => acc + val,
+        Operation::Subtraction => acc - val,
+        Operation::Multiplication => acc * val,
+        Operation::Division => {
+            if val == RBig::ZERO {
+                return OperandValue::Undefined;
+            }
+
+            acc / val
+        }
+        Operation::Equals => return OperandValue::Undefined,
+    };
+
+    OperandValue::Number(NumericValue::exact(value))
+}
+
+impl Default for Calculation {
+    fn default() -> Self {
+        Self {
+            past_operands: vec![],
+            operands: vec![Operand::default()],
+        }
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Addition => f.write_str("+"),
+            Operation::Subtraction => f.write_str("-"),
+            Operation::Multiplication => f.write_str("⨉"),
+            Operation::Division => f.write_str("÷"),
+            Operation::Equals => f.write_str("＝"),
+        }
+    }
+}
+
+impl Operation {
+    /// The name of the operation for screen readers.
+    pub fn spoken_name(&self) -> &'static str {
+        match self {
+            Operation::Addition => "plus",
+            Operation::Subtraction => "minus",
+            Operation::Multiplication => "times",
+            Operation::Division => "divided by",
+            Operation::Equals => "equals",
+        }
+    }
+}
+
+impl OperandValue {
+    fn heap_size(&self) -> usize {
+        match self {
+            OperandValue::Number(value) => value.heap_size(),
+            OperandValue::Complex(value) => value.heap_size(),
+            OperandValue::Duration(value) => value.heap_size(),
+            OperandValue::Undefined => 0,
+        }
+    }
+}
+
+impl Default for OperandValue {
+    fn default() -> Self {
+        OperandValue::Number(NumericValue::default())
+    }
+}
+
+impl Display for OperandValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OperandValue::Number(v) => f.write_fmt(format_args!("{}", v)),
+            OperandValue::Complex(v) => f.write_fmt(format_args!("{}", v)),
+            OperandValue::Duration(v) => f.write_fmt(format_args!("{}", v)),
+            OperandValue::Undefined => f.write_str("Undefined"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_sum_calculation {
+    use super::*;
+
+    #[test]
+    fn calculate_with_only_one_operand() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new(dbig!(5))),
+            }],
+        };
+        calculation.calculate();
+
+        assert_eq!(calculation, calculation.clone());
+    }
+
+    #[test]
+    fn calculate_with_only_one_operand_and_operation() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: Some(Operation::Addition),
+                value: OperandValue::Number(NumericValue::new(dbig!(5))),
+            }],
+        };
+        calculation.calculate();
+
+        assert_eq!(calculation, calculation.clone());
+    }
+
+    #[test]
+    fn calculate_sum_two_values() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![
+                Operand {
+                    operation: Some(Operation::Addition),
+                    value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                },
+                Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                },
+            ],
+        };
+        calculation.calculate();
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![
+                    Operand {
+                        operation: Some(Operation::Addition),
+                        value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                    },
+                    Operand {
+                        operation: None,
+                        value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                    }
+                ],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(10))),
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn calculate_sum_three_values() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![
+                Operand {
+                    operation: Some(Operation::Addition),
+                    value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                },
+                Operand {
+                    operation: Some(Operation::Addition),
+                    value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                },
+                Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(5.5))),
+                },
+            ],
+        };
+        calculation.calculate();
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![
+                    Operand {
+                        operation: Some(Operation::Addition),
+                        value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                    },
+                    Operand {
+                        operation: Some(Operation::Addition),
+                        value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                    },
+                    Operand {
+                        operation: None,
+                        value: OperandValue::Number(NumericValue::new(dbig!(5.5))),
+                    },
+                ],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(15.5))),
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn calculate_with_negative_value() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![
+                Operand {
+                    operation: Some(Operation::Addition),
+                    value: OperandValue::Number(NumericValue::new(dbig!(-10))),
+                },
+                Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                },
+            ],
+        };
+        calculation.calculate();
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![
+                    Operand {
+                        operation: Some(Operation::Addition),
+                        value: OperandValue::Number(NumericValue::new(dbig!(-10))),
+                    },
+                    Operand {
+                        operation: None,
+                        value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                    }
+                ],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(-5))),
+                }]
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_append_number {
+    use super::*;
+
+    #[test]
+    fn append_when_empty() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: Vec::new(),
+        };
+
+        calculation.append_number(5);
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn append_with_comma() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new_with_comma(dbig!(5))),
+            }],
+        };
+
+        calculation.append_number(5);
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(5.5))),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn append_zero_with_comma() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new_with_comma(dbig!(0))),
+            }],
+        };
+
+        calculation.append_number(5);
+        calculation.append_number(5);
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(0.55))),
+                }],
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod append_operation {
+    use super::*;
+
+    #[test]
+    fn appends_when_empty() {
+        let mut calculation = Calculation::default();
+        calculation.append_operation(Operation::Addition);
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: Some(Operation::Addition),
+                    value: OperandValue::default(),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn appends_when_having_value() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        calculation.append_operation(Operation::Addition);
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: Some(Operation::Addition),
+                    value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn overrides_operation_on_multiple_appends() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_operation(Operation::Subtraction);
+        calculation.append_operation(Operation::Multiplication);
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: Some(Operation::Multiplication),
+                    value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn appends_operation_on_multiple_operands() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        calculation.append_operation(Operation::Multiplication);
+        calculation.append_number(10);
+        calculation.append_operation(Operation::Subtraction);
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![
+                    Operand {
+                        operation: Some(Operation::Multiplication),
+                        value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                    },
+                    Operand {
+                        operation: Some(Operation::Subtraction),
+                        value: OperandValue::Number(NumericValue::new(dbig!(10))),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn clears_previous_operands() {
+        let mut calculation = Calculation {
+            past_operands: vec![
+                Operand {
+                    operation: Some(Operation::Multiplication),
+                    value: OperandValue::Number(NumericValue::new(dbig!(2))),
+                },
+                Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(2))),
+                },
+            ],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new(dbig!(5))),
+            }],
+        };
+
+        calculation.append_operation(Operation::Addition);
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: Some(Operation::Addition),
+                    value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                }],
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_is_empty {
+    use dashu::dbig;
+
+    use super::*;
+
+    #[test]
+    fn is_empty_when_no_operands() {
+        let calculation = Calculation::default();
+
+        assert!(calculation.is_empty());
+    }
+
+    #[test]
+    fn is_empty_when_having_past_operands_and_result() {
+        let calculation = Calculation {
+            past_operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new(dbig!(1))),
+            }],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new(dbig!(1))),
+            }],
+        };
+
+        assert!(calculation.is_empty());
+    }
+
+    #[test]
+    fn is_empty_when_operand_value_is_zero() {
+        let calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::default(),
+            }],
+        };
+
+        assert!(calculation.is_empty());
+    }
+
+    #[test]
+    fn is_not_empty_when_having_operands() {
+        let calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new(dbig!(1))),
+            }],
+        };
+
+        assert!(!calculation.is_empty());
+    }
+
+    #[test]
+    fn is_not_empty_when_having_symbol() {
+        let calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: Some(Operation::Multiplication),
+                value: OperandValue::default(),
+            }],
+        };
+
+        assert!(!calculation.is_empty());
+    }
+
+    #[test]
+    fn is_not_empty_when_having_multiple_operands() {
+        let calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand::default(), Operand::default()],
+        };
+
+        assert!(!calculation.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_remove_last {
+    use super::*;
+
+    #[test]
+    fn remove_when_having_number() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new(dbig!(155))),
+            }],
+        };
+
+        calculation.remove_last();
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(15))),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn remove_to_reach_zero() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new(dbig!(155))),
+            }],
+        };
+
+        calculation.remove_last();
+        calculation.remove_last();
+        calculation.remove_last();
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(0))),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn remove_does_not_get_further_than_zero() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new(dbig!(155))),
+            }],
+        };
+
+        calculation.remove_last();
+        calculation.remove_last();
+        calculation.remove_last();
+        calculation.remove_last();
+        calculation.remove_last();
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(0))),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn removes_operation_before_number() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: Some(Operation::Addition),
+                value: OperandValue::Number(NumericValue::new(dbig!(155))),
+            }],
+        };
+
+        calculation.remove_last();
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(155))),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn remove_when_float_leaves_the_comma() {
+        let mut calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![Operand {
+                operation: None,
+                value: OperandValue::Number(NumericValue::new(dbig!(155.5))),
+            }],
+        };
+
+        calculation.remove_last();
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new_with_comma(dbig!(155))),
+                }],
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_current_value {
+    use super::*;
+
+    #[test]
+    fn detects_pending_operation() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+
+        assert!(!calculation.has_pending_operation());
+
+        calculation.append_operation(Operation::Addition);
+
+        assert!(calculation.has_pending_operation());
+    }
+
+    #[test]
+    fn defaults_to_zero() {
+        let calculation = Calculation {
+            past_operands: vec![],
+            operands: vec![],
+        };
+
+        assert_eq!(calculation.current_value(), NumericValue::default());
+    }
+
+    #[test]
+    fn returns_last_operand() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_number(7);
+
+        assert_eq!(calculation.current_value(), NumericValue::new(dbig!(7)));
+    }
+
+    #[test]
+    fn replaces_last_operand() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_number(7);
+
+        calculation.replace_current_value(NumericValue::new(dbig!(2.5)));
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![
+                    Operand {
+                        operation: Some(Operation::Addition),
+                        value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                    },
+                    Operand {
+                        operation: None,
+                        value: OperandValue::Number(NumericValue::new(dbig!(2.5))),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn pushes_after_pending_operation() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        calculation.append_operation(Operation::Multiplication);
+
+        calculation.replace_current_value(NumericValue::new(dbig!(3)));
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![
+                    Operand {
+                        operation: Some(Operation::Multiplication),
+                        value: OperandValue::Number(NumericValue::new(dbig!(5))),
+                    },
+                    Operand {
+                        operation: None,
+                        value: OperandValue::Number(NumericValue::new(dbig!(3))),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn clears_past_operands() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_number(5);
+        calculation.calculate();
+
+        calculation.replace_current_value(NumericValue::new(dbig!(3)));
+
+        assert_eq!(
+            calculation,
+            Calculation {
+                past_operands: vec![],
+                operands: vec![Operand {
+                    operation: None,
+                    value: OperandValue::Number(NumericValue::new(dbig!(3))),
+                }],
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_repeat_equals {
+    use super::*;
+
+    fn keyed(left: usize, operation: Operation, right: usize) -> Calculation {
+        let mut calculation = Calculation::default();
+        calculation.append_number(left);
+        calculation.append_operation(operation);
+        calculation.append_number(right);
+
+        calculation
+    }
+
+    fn results(calculation: &mut Calculation, presses: usize) -> Vec<NumericValue> {
+        (0..presses)
+            .map(|_| {
+                calculation.calculate();
+                calculation.current_value()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn repeats_last_addition() {
+        let mut calculation = keyed(5, Operation::Addition, 3);
+
+        assert_eq!(
+            results(&mut calculation, 3),
+            vec![
+                NumericValue::new(dbig!(8)),
+                NumericValue::new(dbig!(11)),
+                NumericValue::new(dbig!(14)),
+            ]
+        );
+        assert_eq!(calculation.past_operations_string(), String::from("11+3"));
+    }
+
+    #[test]
+    fn repeats_last_subtraction() {
+        let mut calculation = keyed(9, Operation::Subtraction, 2);
+
+        assert_eq!(
+            results(&mut calculation, 3),
+            vec![
+                NumericValue::new(dbig!(7)),
+                NumericValue::new(dbig!(5)),
+                NumericValue::new(dbig!(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn multiplication_keeps_first_factor_constant() {
+        let mut calculation = keyed(3, Operation::Multiplication, 5);
+
+        assert_eq!(
+            results(&mut calculation, 3),
+            vec![
+                NumericValue::new(dbig!(15)),
+                NumericValue::new(dbig!(45)),
+                NumericValue::new(dbig!(135)),
+            ]
+        );
+    }
+
+    #[test]
+    fn division_keeps_divisor_constant() {
+        let mut calculation = keyed(8, Operation::Division, 2);
+
+        assert_eq!(
+            results(&mut calculation, 3),
+            vec![
+                NumericValue::new(dbig!(4)),
+                NumericValue::new(dbig!(2)),
+                NumericValue::new(dbig!(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn applies_constant_to_new_entry() {
+        let mut calculation = keyed(3, Operation::Multiplication, 5);
+        calculation.calculate();
+
+        calculation.start_over();
+        calculation.append_number(6);
+        calculation.calculate();
+
+        assert_eq!(calculation.current_value(), NumericValue::new(dbig!(18)));
+
+        let mut calculation = keyed(8, Operation::Division, 2);
+        calculation.calculate();
+
+        calculation.start_over();
+        calculation.append_number(6);
+        calculation.calculate();
+
+        assert_eq!(calculation.current_value(), NumericValue::new(dbig!(3)));
+    }
+
+    #[test]
+    fn entry_after_result_is_not_empty() {
+        let mut calculation = keyed(5, Operation::Addition, 3);
+        calculation.calculate();
+
+        assert!(calculation.is_empty());
+
+        calculation.start_over();
+        calculation.append_number(4);
+
+        assert!(!calculation.is_empty());
+        assert_eq!(calculation.past_operations_string(), String::from("5+3＝"));
+    }
+
+    #[test]
+    fn reports_repeated_operation() {
+        let mut calculation = keyed(5, Operation::Addition, 3);
+        assert_eq!(calculation.repeated_operation(), None);
+
+        calculation.calculate();
+        assert_eq!(
+            calculation.repeated_operation(),
+            Some((
+                Operation::Addition,
+                OperandValue::Number(NumericValue::new(dbig!(3)))
+            ))
+        );
+
+        let mut calculation = keyed(2, Operation::Multiplication, 4);
+        calculation.calculate();
+        assert_eq!(
+            calculation.repeated_operation(),
+            Some((
+                Operation::Multiplication,
+                OperandValue::Number(NumericValue::new(dbig!(2)))
+            ))
+        );
+
+        calculation.append_operation(Operation::Subtraction);
+        assert_eq!(calculation.repeated_operation(), None);
+    }
+
+    #[test]
+    fn new_operation_repeats_its_own_operand() {
+        let mut calculation = keyed(5, Operation::Addition, 3);
+        calculation.calculate();
+        calculation.append_operation(Operation::Multiplication);
+        calculation.append_number(2);
+        calculation.calculate();
+
+        assert_eq!(
+            results(&mut calculation, 1),
+            vec![NumericValue::new(dbig!(128))]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_rational {
+    use super::*;
+
+    fn exact(numerator: i32, denominator: u32) -> NumericValue {
+        NumericValue::exact(RBig::from_parts(numerator.into(), denominator.into()))
+    }
+
+    fn key(calculation: &mut Calculation, keys: &str) {
+        for key in keys.chars() {
+            match key {
+                '0'..='9' => calculation.append_number(key.to_digit(10).unwrap() as usize),
+                '|' => calculation.add_fraction_bar(),
+                '+' => calculation.append_operation(Operation::Addition),
+                '-' => calculation.append_operation(Operation::Subtraction),
+                '*' => calculation.append_operation(Operation::Multiplication),
+                '/' => calculation.append_operation(Operation::Division),
+                _ => panic!("Unknown key {}", key),
+            }
+        }
+    }
+
+    #[test]
+    fn decimal_division_is_inexact() {
+        let mut calculation = Calculation::default();
+        key(&mut calculation, "1/3*3");
+        calculation.calculate();
+
+        assert_ne!(calculation.current_value().val(), &dbig!(1));
+    }
+
+    #[test]
+    fn exact_division_round_trips() {
+        let mut calculation = Calculation::default();
+        key(&mut calculation, "1/3*3");
+        calculation.make_exact();
+        calculation.calculate();
+
+        assert_eq!(calculation.current_value(), exact(1, 1));
+        assert_eq!(calculation.current_operation_string(), String::from("1"));
+    }
+
+    #[test]
+    fn shows_proper_and_mixed_fractions() {
+        assert_eq!(exact(1, 3).to_string(), "1/3");
+        assert_eq!(exact(5, 3).to_string(), "1 2/3");
+        assert_eq!(exact(-5, 3).to_string(), "-1 2/3");
+        assert_eq!(exact(6, 3).to_string(), "2");
+    }
+
+    #[test]
+    fn toggles_decimal_display() {
+        let mut value = exact(1, 4);
+        value.toggle_fraction();
+
+        assert_eq!(value.to_string(), "0.25");
+
+        value.toggle_fraction();
+
+        assert_eq!(value.to_string(), "1/4");
+    }
+
+    #[test]
+    fn enters_fraction_with_bar_key() {
+        let mut calculation = Calculation::default();
+        key(&mut calculation, "2|3");
+
+        assert_eq!(calculation.current_operation_string(), String::from("2/3"));
+        assert_eq!(
+            calculation.current_value().to_exact(),
+            Some(RBig::from_parts(2.into(), 3u8.into()))
+        );
+    }
+
+    #[test]
+    fn enters_mixed_number_with_bar_key() {
+        let mut calculation = Calculation::default();
+        key(&mut calculation, "1|2|3");
+
+        assert_eq!(
+            calculation.current_operation_string(),
+            String::from("1 2/3")
+        );
+        assert_eq!(
+            calculation.current_value().to_exact(),
+            Some(RBig::from_parts(5.into(), 3u8.into()))
+        );
+    }
+
+    #[test]
+    fn fraction_entry_promotes_decimal_operands() {
+        let mut calculation = Calculation::default();
+        key(&mut calculation, "1|3+1|6");
+        calculation.calculate();
+
+        assert_eq!(calculation.current_value(), exact(1, 2));
+    }
+
+    #[test]
+    fn exact_division_by_zero_is_undefined() {
+        let mut calculation = Calculation::default();
+        key(&mut calculation, "1|3/0");
+        calculation.calculate();
+
+        assert_eq!(
+            calculation.current_operation_string(),
+            String::from("Undefined")
+        );
+    }
+
+    #[test]
+    fn backspace_clears_fraction() {
+        let mut calculation = Calculation::default();
+        key(&mut calculation, "2|3");
+        calculation.remove_last();
+
+        assert_eq!(calculation.current_value(), NumericValue::default());
+    }
+}
+
+#[cfg(test)]
+mod test_complex_operands {
+    use super::*;
+
+    fn complex(re: i32, im: i32) -> OperandValue {
+        OperandValue::Complex(Complex::new(DBig::from(re), DBig::from(im)))
+    }
+
+    fn result(calculation: &Calculation) -> OperandValue {
+        calculation.operands.last().unwrap().value.clone()
+    }
+
+    #[test]
+    fn enters_imaginary_number() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(4);
+        calculation.append_imaginary();
+
+        assert_eq!(result(&calculation), complex(0, 4));
+        assert_eq!(calculation.current_operation_string(), String::from("4i"));
+    }
+
+    #[test]
+    fn adds_real_and_imaginary_parts() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(3);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_number(4);
+        calculation.append_imaginary();
+        calculation.calculate();
+
+        assert_eq!(result(&calculation), complex(3, 4));
+        assert_eq!(calculation.current_operation_string(), String::from("3+4i"));
+    }
+
+    #[test]
+    fn collapses_real_results() {
+        let mut calculation = Calculation::default();
+        calculation.append_imaginary();
+        calculation.append_operation(Operation::Multiplication);
+        calculation.append_imaginary();
+        calculation.calculate();
+
+        assert_eq!(
+            result(&calculation),
+            OperandValue::Number(NumericValue::new(dbig!(-1)))
+        );
+    }
+
+    #[test]
+    fn square_root_of_negative_is_imaginary() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(9);
+        calculation.append_operation(Operation::Subtraction);
+        calculation.append_number(1);
+        calculation.append_number(8);
+        calculation.calculate();
+        calculation.square_root();
+
+        assert_eq!(result(&calculation), complex(0, 3));
+    }
+
+    #[test]
+    fn square_root_of_positive_is_real() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(1);
+        calculation.append_number(6);
+        calculation.square_root();
+
+        assert_eq!(
+            result(&calculation),
+            OperandValue::Number(NumericValue::new(dbig!(4)))
+        );
+    }
+
+    #[test]
+    fn complex_division_by_zero_is_undefined() {
+        let mut calculation = Calculation::default();
+        calculation.append_imaginary();
+        calculation.append_operation(Operation::Division);
+        calculation.append_number(0);
+        calculation.calculate();
+
+        assert_eq!(result(&calculation), OperandValue::Undefined);
+    }
+
+    #[test]
+    fn toggles_polar_display() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(2);
+        calculation.append_imaginary();
+        calculation.toggle_polar();
+
+        assert_eq!(
+            calculation.current_operation_string(),
+            String::from("2∠90°")
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_duration_operands {
+    use super::*;
+
+    fn enter(calculation: &mut Calculation, value: usize, unit: TimeUnit) {
+        for digit in value.to_string().chars() {
+            calculation.append_number(digit.to_digit(10).unwrap() as usize);
+        }
+        calculation.append_time_unit(unit);
+    }
+
+    #[test]
+    fn enters_duration() {
+        let mut calculation = Calculation::default();
+        enter(&mut calculation, 1, TimeUnit::Hours);
+        enter(&mut calculation, 25, TimeUnit::Minutes);
+
+        assert_eq!(
+            calculation.current_operation_string(),
+            String::from("1h 25m")
+        );
+    }
+
+    #[test]
+    fn adds_durations() {
+        let mut calculation = Calculation::default();
+        enter(&mut calculation, 1, TimeUnit::Hours);
+        enter(&mut calculation, 25, TimeUnit::Minutes);
+        calculation.append_operation(Operation::Addition);
+        enter(&mut calculation, 47, TimeUnit::Minutes);
+        calculation.calculate();
+
+        assert_eq!(
+            calculation.past_operations_string(),
+            String::from("1h 25m+47m")
+        );
+        assert_eq!(
+            calculation.current_operation_string(),
+            String::from("2h 12m")
+        );
+    }
+
+    #[test]
+    fn scales_duration_by_number() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(3);
+        calculation.append_operation(Operation::Multiplication);
+        enter(&mut calculation, 20, TimeUnit::Minutes);
+        calculation.calculate();
+
+        assert_eq!(calculation.current_operation_string(), String::from("1h"));
+    }
+
+    #[test]
+    fn divides_durations_into_number() {
+        let mut calculation = Calculation::default();
+        enter(&mut calculation, 2, TimeUnit::Hours);
+        calculation.append_operation(Operation::Division);
+        enter(&mut calculation, 40, TimeUnit::Minutes);
+        calculation.calculate();
+
+        assert_eq!(
+            calculation.current_operand_value(),
+            OperandValue::Number(NumericValue::new(dbig!(3)))
+        );
+    }
+
+    #[test]
+    fn adding_number_to_duration_is_undefined() {
+        let mut calculation = Calculation::default();
+        enter(&mut calculation, 2, TimeUnit::Hours);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_number(5);
+        calculation.calculate();
+
+        assert_eq!(calculation.current_operand_value(), OperandValue::Undefined);
+    }
+
+    #[test]
+    fn removes_pending_digits_then_duration() {
+        let mut calculation = Calculation::default();
+        enter(&mut calculation, 1, TimeUnit::Hours);
+        calculation.append_number(2);
+        calculation.remove_last();
+
+        assert_eq!(calculation.current_operation_string(), String::from("1h"));
+
+        calculation.remove_last();
+
+        assert_eq!(calculation.current_operand_value(), OperandValue::default());
+    }
+}
+
+#[cfg(test)]
+mod test_edit {
+    use super::*;
+
+    fn entry(keys: &str) -> Calculation {
+        let mut calculation = Calculation::default();
+
+        for key in keys.chars() {
+            match key {
+                '+' => calculation.append_operation(Operation::Addition),
+                '-' => calculation.append_operation(Operation::Subtraction),
+                '*' => calculation.append_operation(Operation::Multiplication),
+                '.' => calculation.add_comma(),
+                digit => calculation.append_number(digit.to_digit(10).unwrap() as usize),
+            }
+        }
+
+        calculation
+    }
+
+    #[test]
+    fn inserts_digits_in_the_middle() {
+        let edited = entry("12+34").edit(1..1, "5").unwrap();
+
+        assert_eq!(edited.current_operation_string(), "152+34");
+        assert_eq!(edited, entry("152+34"));
+    }
+
+    #[test]
+    fn replaces_and_deletes_ranges() {
+        let calculation = entry("12+34");
+
+        assert_eq!(calculation.edit(2..3, "⨉").unwrap(), entry("12*34"));
+        assert_eq!(calculation.edit(1..4, "").unwrap(), entry("14"));
+        assert_eq!(calculation.edit(3..5, "").unwrap(), entry("12+"));
+        assert_eq!(calculation.edit(0..5, "").unwrap(), Calculation::default());
+    }
+
+    #[test]
+    fn splits_operands_with_operations() {
+        let mut edited = entry("1234").edit(2..2, "+").unwrap();
+
+        assert_eq!(edited, entry("12+34"));
+        edited.calculate();
+        assert_eq!(edited.current_value(), NumericValue::new(dbig!(46)));
+    }
+
+    #[test]
+    fn keeps_signs_and_commas() {
+        let calculation = entry("5-3");
+
+        assert_eq!(
+            calculation
+                .edit(2..2, "-")
+                .unwrap()
+                .current_operation_string(),
+            "5--3"
+        );
+        assert_eq!(
+            entry("5").edit(0..0, "-").unwrap().current_value(),
+            NumericValue::new(dbig!(-5))
+        );
+        assert_eq!(calculation.edit(1..1, ".").unwrap(), entry("5.-3"));
+        assert_eq!(entry("1.5").edit(3..3, "2").unwrap(), entry("1.52"));
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        assert!(entry("1.5").edit(0..0, ".").is_none());
+        assert!(entry("12").edit(1..1, "x").is_none());
+        assert!(entry("5+3").edit(2..3, "-").is_none());
+
+        let mut fraction = entry("1");
+        fraction.add_fraction_bar();
+        assert!(!fraction.is_editable());
+        assert!(fraction.edit(0..0, "2").is_none());
+    }
+
+    #[test]
+    fn edits_durations() {
+        let mut calculation = Calculation::parse("1h 25m+47m").unwrap();
+        assert!(calculation.is_editable());
+
+        // Changes 47m to 17m, then replaces the whole second operand.
+        let edited = calculation.edit(7..8, "1").unwrap();
+        assert_eq!(edited.current_operation_string(), "1h 25m+17m");
+        let edited = calculation.edit(7..10, "2h").unwrap();
+        assert_eq!(edited.current_operation_string(), "1h 25m+2h");
+
+        calculation.calculate();
+        assert_eq!(calculation.current_operation_string(), "2h 12m");
+        assert!(calculation.edit(0..0, "x").is_none());
+    }
+
+    #[test]
+    fn clears_past_operations() {
+        let mut calculation = entry("2+3");
+        calculation.calculate();
+
+        let edited = calculation.edit(0..0, "1").unwrap();
+        assert_eq!(edited, entry("15"));
+        assert_eq!(edited.past_operations_string(), "");
+    }
+}
+
+#[cfg(test)]
+mod test_undefined {
+    use super::*;
+
+    #[test]
+    fn typing_starts_over() {
+        let mut calculation = Calculation::parse("1/0=").unwrap();
+        calculation.append_number(3);
+
+        assert_eq!(calculation.current_operation_string(), "3");
+
+        let mut calculation = Calculation::parse("1/0=").unwrap();
+        calculation.add_comma();
+
+        assert_eq!(calculation.current_operation_string(), "0.");
+    }
+
+    #[test]
+    fn ignores_equals_as_an_operation() {
+        let mut calculation = Calculation::parse("2").unwrap();
+        calculation.append_operation(Operation::Equals);
+        calculation.append_number(3);
+        calculation.calculate();
+
+        assert_eq!(calculation.current_operation_string(), "23");
+    }
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn matches_keypad_entry() {
+        let mut keyed = Calculation::default();
+        keyed.append_number(1);
+        keyed.add_comma();
+        keyed.append_number(5);
+        keyed.append_operation(Operation::Multiplication);
+        keyed.append_number(4);
+
+        assert_eq!(Calculation::parse("1.5⨉4").unwrap(), keyed);
+        assert_eq!(Calculation::parse(" 1.5 * 4 ").unwrap(), keyed);
+        assert_eq!(Calculation::parse("").unwrap(), Calculation::default());
+    }
+
+    #[test]
+    fn calculates_with_trailing_equals() {
+        let calculation = Calculation::parse("10 / 4 - 1 =").unwrap();
+
+        assert_eq!(calculation.current_operation_string(), "1.5");
+        assert_eq!(calculation.past_operations_string(), "10÷4-1");
+        assert_eq!(
+            Calculation::parse("1÷0=")
+                .unwrap()
+                .current_operation_string(),
+            "Undefined"
+        );
+    }
+
+    #[test]
+    fn parses_durations() {
+        for text in ["1h 25m+47m", "2h÷40m", "3⨉20m", "-1m 30s-1h 2"] {
+            assert_eq!(
+                Calculation::parse(text).unwrap().current_operation_string(),
+                text
+            );
+        }
+
+        let mut keyed = Calculation::default();
+        keyed.append_number(1);
+        keyed.append_time_unit(TimeUnit::Hours);
+        keyed.append_number(2);
+        keyed.append_number(5);
+        keyed.append_time_unit(TimeUnit::Minutes);
+        assert_eq!(Calculation::parse("1h 25m").unwrap(), keyed);
+
+        let calculation = Calculation::parse("1h 25m + 47m =").unwrap();
+        assert_eq!(calculation.past_operations_string(), "1h 25m+47m");
+        assert_eq!(calculation.current_operation_string(), "2h 12m");
+    }
+
+    #[test]
+    fn rejects_other_input() {
+        assert_eq!(Calculation::parse("2+x"), None);
+        assert_eq!(Calculation::parse("2+h"), None);
+        assert_eq!(Calculation::parse("=="), None);
+    }
+}
+
+#[cfg(test)]
+mod test_preview {
+    use super::*;
+
+    fn number(value: DBig) -> OperandValue {
+        OperandValue::Number(NumericValue::new(value))
+    }
+
+    #[test]
+    fn previews_without_changing_the_entry() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_number(3);
+        let before = calculation.clone();
+
+        assert_eq!(calculation.preview(), Some(number(dbig!(8))));
+        assert_eq!(calculation, before);
+
+        calculation.append_operation(Operation::Multiplication);
+        calculation.append_number(2);
+        assert_eq!(calculation.preview(), Some(number(dbig!(16))));
+    }
+
+    #[test]
+    fn hides_incomplete_and_undefined_results() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        assert_eq!(calculation.preview(), None);
+
+        calculation.append_operation(Operation::Division);
+        assert_eq!(calculation.preview(), None);
+
+        calculation.append_number(0);
+        assert_eq!(calculation.preview(), None);
+
+        calculation.calculate();
+        assert_eq!(calculation.preview(), None);
+    }
+}
+
+#[cfg(test)]
+mod test_footprint {
+    use super::*;
+    use dashu::integer::IBig;
+
+    fn holding(value: NumericValue) -> Calculation {
+        let mut calculation = Calculation::default();
+        calculation.replace_current_value(value);
+
+        calculation
+    }
+
+    #[test]
+    fn counts_the_digits_of_numbers() {
+        let small = holding(NumericValue::new(dbig!(7))).footprint();
+        let decimal = DBig::from_str(&"7".repeat(1000)).unwrap();
+        let exact = RBig::from(IBig::from(10u8).pow(1000));
+
+        // A thousand decimal digits take about 415 bytes.
+        assert!(holding(NumericValue::new(decimal)).footprint() > small + 400);
+        assert!(holding(NumericValue::exact(exact)).footprint() > small + 400);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::numeric_value::{decimal_heap_size, precise, PRECISION};
+use dashu::{base::SquareRoot, Decimal};
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+const ANGLE_PLACES: i32 = 4;
+
+/// A complex number, shown in rectangular or polar form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Complex {
+    re: Decimal,
+    im: Decimal,
+    polar: bool,
+}
+
+impl Complex {
+    /// A complex number from its real and imaginary parts.
+    pub fn new(re: Decimal, im: Decimal) -> Self {
+        Complex {
+            re,
+            im,
+            polar: false,
+        }
+    }
+
+    pub(crate) fn heap_size(&self) -> usize {
+        decimal_heap_size(&self.re) + decimal_heap_size(&self.im)
+    }
+
+    /// The real part.
+    pub fn re(&self) -> &Decimal {
+        &self.re
+    }
+
+    /// The imaginary part.
+    pub fn im(&self) -> &Decimal {
+        &self.im
+    }
+
+    /// Whether the imaginary part is zero.
+    pub fn is_real(&self) -> bool {
+        self.im == Decimal::ZERO
+    }
+
+    /// Switches between rectangular and polar form.
+    pub fn toggle_polar(&mut self) {
+        self.polar = !self.polar;
+    }
+
+    /// The sum of both numbers.
+    pub fn add(&self, other: &Complex) -> Complex {
+        Complex::new(
+            precise(&self.re).add(&other.re),
+            precise(&self.im).add(&other.im),
+        )
+    }
+
+    /// The difference of both numbers.
+    pub fn sub(&self, other: &Complex) -> Complex {
+        Complex::new(
+            precise(&self.re).sub(&other.re),
+            precise(&self.im).sub(&other.im),
+        )
+    }
+
+    /// The product of both numbers.
+    pub fn mul(&self, other: &Complex) -> Complex {
+        Complex::new(
+            precise(&self.re)
+                .mul(&other.re)
+                .sub(precise(&self.im).mul(&other.im)),
+            precise(&self.re)
+                .mul(&other.im)
+                .add(precise(&self.im).mul(&other.re)),
+        )
+    }
+
+    /// The quotient of both numbers, or None when dividing by zero.
+    pub fn div(&self, other: &Complex) -> Option<Complex> {
+        let denominator = precise(&other.re)
+            .mul(&other.re)
+            .add(precise(&other.im).mul(&other.im));
+
+        if denominator == Decimal::ZERO {
+            return None;
+        }
+
+        let re = precise(&self.re)
+            .mul(&other.re)
+            .add(precise(&self.im).mul(&other.im));
+        let im = precise(&self.im)
+            .mul(&other.re)
+            .sub(precise(&self.re).mul(&other.im));
+
+        Some(Complex::new(
+            re.div(&denominator).with_precision(PRECISION).value(),
+            im.div(&denominator).with_precision(PRECISION).value(),
+        ))
+    }
+
+    /// The distance from zero.
+    pub fn magnitude(&self) -> Decimal {
+        sqrt(
+            &precise(&self.re)
+                .mul(&self.re)
+                .add(precise(&self.im).mul(&self.im)),
+        )
+    }
+
+    /// The angle from the positive real axis, in degrees.
+    pub fn angle_degrees(&self) -> f64 {
+        let re = self.re.to_f64().value();
+        let im = self.im.to_f64().value();
+
+        im.atan2(re).to_degrees()
+    }
+
+    /// The principal square root.
+    pub fn sqrt(&self) -> Complex {
+        let magnitude = self.magnitude();
+        let two = Decimal::from(2u8);
+
+        let re = sqrt(&precise(&magnitude).add(&self.re).div(&two));
+        let im = sqrt(&precise(&magnitude).sub(&self.re).div(&two));
+
+        if self.im < Decimal::ZERO {
+            Complex::new(re, im.neg())
+        } else {
+            Complex::new(re, im)
+        }
+    }
+}
+
+impl From<&Decimal> for Complex {
+    fn from(value: &Decimal) -> Self {
+        Complex::new(value.clone(), Decimal::ZERO)
+    }
+}
+
+/// The square root of a decimal, or zero for numbers that are not positive.
+pub fn sqrt(value: &Decimal) -> Decimal {
+    if *value <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+
+    precise(value).sqrt()
+}
+
+fn write_imaginary(f: &mut std::fmt::Formatter<'_>, im: &Decimal) -> std::fmt::Result {
+    if *im == Decimal::ONE {
+        f.write_str("i")
+    } else if *im == Decimal::NEG_ONE {
+        f.write_str("-i")
+    } else {
+        write!(f, "{}i", im)
+    }
+}
+
+impl Display for Complex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.polar {
+            let scale = 10f64.powi(ANGLE_PLACES);
+            let angle = (self.angle_degrees() * scale).round() / scale;
+
+            return write!(f, "{}∠{}°", self.magnitude(), angle);
+        }
+
+        if self.re == Decimal::ZERO {
+            return write_imaginary(f, &self.im);
+        }
+
+        write!(f, "{}", self.re)?;
+
+        if self.im >= Decimal::ZERO {
+            f.write_str("+")?;
+        }
+
+        write_imaginary(f, &self.im)
+    }
+}
+
+#[cfg(test)]
+mod test_complex {
+    use super::*;
+
+    fn complex(re: i32, im: i32) -> Complex {
+        Complex::new(Decimal::from(re), Decimal::from(im))
+    }
+
+    #[test]
+    fn multiplies() {
+        assert_eq!(complex(1, 2).mul(&complex(3, -1)), complex(5, 5));
+    }
+
+    #[test]
+    fn divides() {
+        assert_eq!(complex(5, 5).div(&complex(3, -1)), Some(complex(1, 2)));
+        assert_eq!(complex(5, 5).div(&complex(0, 0)), None);
+    }
+
+    #[test]
+    fn takes_principal_square_root() {
+        assert_eq!(complex(-4, 0).sqrt(), complex(0, 2));
+        assert_eq!(complex(3, 4).sqrt(), complex(2, 1));
+        assert_eq!(complex(3, -4).sqrt(), complex(2, -1));
+    }
+
+    #[test]
+    fn shows_rectangular_form() {
+        assert_eq!(complex(3, 4).to_string(), "3+4i");
+        assert_eq!(complex(3, -4).to_string(), "3-4i");
+        assert_eq!(complex(0, 1).to_string(), "i");
+        assert_eq!(complex(0, -2).to_string(), "-2i");
+    }
+
+    #[test]
+    fn shows_polar_form() {
+        let mut value = complex(3, 4);
+        value.toggle_polar();
+
+        assert_eq!(value.to_string(), "5∠53.1301°");
+
+        let mut value = complex(0, -2);
+        value.toggle_polar();
+
+        assert_eq!(value.to_string(), "2∠-90°");
+    }
+}
@@ -0,0 +1,337 @@
+use crate::numeric_value::{decimal_heap_size, precise, PRECISION};
+use dashu::{integer::IBig, Decimal};
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+const SECOND_PLACES: usize = 3;
+
+/// A unit of a duration, from days down to seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum TimeUnit {
+    Days,
+    Hours,
+    Minutes,
+    Seconds,
+}
+
+/// A length of time, shown as `1h 25m`. While typing, digits wait for their unit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Duration {
+    seconds: Decimal,
+    unit: TimeUnit,
+    pending: String,
+}
+
+impl TimeUnit {
+    /// All units, largest first.
+    pub const ALL: [TimeUnit; 4] = [
+        TimeUnit::Days,
+        TimeUnit::Hours,
+        TimeUnit::Minutes,
+        TimeUnit::Seconds,
+    ];
+
+    fn seconds(&self) -> Decimal {
+        match self {
+            TimeUnit::Days => Decimal::from(86400u32),
+            TimeUnit::Hours => Decimal::from(3600u32),
+            TimeUnit::Minutes => Decimal::from(60u8),
+            TimeUnit::Seconds => Decimal::ONE,
+        }
+    }
+
+    fn smaller(&self) -> TimeUnit {
+        match self {
+            TimeUnit::Days => TimeUnit::Hours,
+            TimeUnit::Hours => TimeUnit::Minutes,
+            TimeUnit::Minutes | TimeUnit::Seconds => TimeUnit::Seconds,
+        }
+    }
+}
+
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeUnit::Days => f.write_str("d"),
+            TimeUnit::Hours => f.write_str("h"),
+            TimeUnit::Minutes => f.write_str("m"),
+            TimeUnit::Seconds => f.write_str("s"),
+        }
+    }
+}
+
+impl Duration {
+    /// A duration of `value` in `unit`.
+    pub fn new(value: &Decimal, unit: TimeUnit) -> Self {
+        Duration {
+            seconds: precise(value).mul(unit.seconds()),
+            unit,
+            pending: String::new(),
+        }
+    }
+
+    pub(crate) fn heap_size(&self) -> usize {
+        decimal_heap_size(&self.seconds) + self.pending.capacity()
+    }
+
+    /// A duration of the given number of seconds.
+    pub fn from_seconds(seconds: Decimal) -> Self {
+        Duration::new(&seconds, TimeUnit::Seconds)
+    }
+
+    /// The length in seconds, counting digits typed without a unit in the next
+    /// smaller unit.
+    pub fn seconds(&self) -> Decimal {
+        let pending = Decimal::from_str(&self.pending).unwrap_or(Decimal::ZERO);
+
+        precise(&self.seconds).add(pending.mul(self.unit.smaller().seconds()))
+    }
+
+    /// Types a digit of the next part.
+    pub fn with_digit(&self, num: usize) -> Self {
+        let mut duration = self.clone();
+        duration.pending.push_str(&num.to_string());
+
+        duration
+    }
+
+    /// Types the decimal separator of the next part.
+    pub fn with_comma(&self) -> Self {
+        let mut duration = self.clone();
+
+        if duration.pending.is_empty() {
+            duration.pending.push('0');
+        }
+        if !duration.pending.contains('.') {
+            duration.pending.push('.');
+        }
+
+        duration
+    }
+
+    /// Ends the typed digits with `unit`, or None when none were typed.
+    pub fn with_unit(&self, unit: TimeUnit) -> Option<Self> {
+        let pending = Decimal::from_str(&self.pending).ok()?;
+        let pending = precise(&pending).mul(unit.seconds());
+
+        Some(Duration {
+            seconds: precise(&self.seconds).add(pending),
+            unit,
+            pending: String::new(),
+        })
+    }
+
+    /// Parses a duration as displayed, such as `1h 25m` or `-1m 30s`. Digits
+    /// after the last unit are kept as typed, as in `1h 2`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (negative, text) = match text.trim().strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text.trim()),
+        };
+
+        let mut seconds = Decimal::ZERO;
+        let mut unit = None;
+        let mut number = String::new();
+
+        for c in text.chars().filter(|c| !c.is_whitespace()) {
+            let part = match c {
+                'd' => TimeUnit::Days,
+                'h' => TimeUnit::Hours,
+                'm' => TimeUnit::Minutes,
+                's' => TimeUnit::Seconds,
+                '0'..='9' | '.' => {
+                    number.push(c);
+                    continue;
+                }
+                _ => return None,
+            };
+
+            let value = Decimal::from_str(&std::mem::take(&mut number)).ok()?;
+            seconds = precise(&seconds).add(precise(&value).mul(part.seconds()));
+            unit = Some(part);
+        }
+
+        if number.matches('.').count() > 1 {
+            return None;
+        }
+
+        Some(Duration {
+            seconds: if negative { seconds.neg() } else { seconds },
+            unit: unit?,
+            pending: number,
+        })
+    }
+
+    /// Removes the last typed digit, or None when there is none.
+    pub fn without_last(&self) -> Option<Self> {
+        let mut duration = self.clone();
+        duration.pending.pop()?;
+
+        Some(duration)
+    }
+
+    /// The sum of both durations.
+    pub fn add(&self, other: &Duration) -> Duration {
+        Duration::from_seconds(self.seconds().add(other.seconds()))
+    }
+
+    /// The difference of both durations.
+    pub fn sub(&self, other: &Duration) -> Duration {
+        Duration::from_seconds(self.seconds().sub(other.seconds()))
+    }
+
+    /// The duration scaled by `factor`.
+    pub fn mul(&self, factor: &Decimal) -> Duration {
+        Duration::from_seconds(self.seconds().mul(factor).with_precision(PRECISION).value())
+    }
+
+    /// The duration divided by `divisor`, or None when it is zero.
+    pub fn div(&self, divisor: &Decimal) -> Option<Duration> {
+        if *divisor == Decimal::ZERO {
+            return None;
+        }
+
+        Some(Duration::from_seconds(
+            self.seconds()
+                .div(divisor)
+                .with_precision(PRECISION)
+                .value(),
+        ))
+    }
+
+    /// How many times `other` fits in this duration, or None when it is zero.
+    pub fn ratio(&self, other: &Duration) -> Option<Decimal> {
+        let divisor = other.seconds();
+        if divisor == Decimal::ZERO {
+            return None;
+        }
+
+        Some(
+            self.seconds()
+                .div(divisor)
+                .with_precision(PRECISION)
+                .value(),
+        )
+    }
+}
+
+impl Display for Duration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scale = Decimal::from(10u8).powi(SECOND_PLACES.into());
+        let total = precise(&self.seconds).mul(&scale).round().div(&scale);
+
+        let sign = if total < Decimal::ZERO { "-" } else { "" };
+        let total = if total < Decimal::ZERO {
+            total.neg()
+        } else {
+            total
+        };
+
+        let whole = total.trunc().to_int().value();
+        let fract = precise(&total).sub(total.trunc());
+
+        let days = &whole / IBig::from(86400);
+        let hours = &whole % IBig::from(86400) / IBig::from(3600);
+        let minutes = &whole % IBig::from(3600) / IBig::from(60);
+        let seconds = &whole % IBig::from(60);
+
+        let mut parts = vec![];
+        if days != IBig::ZERO {
+            parts.push(format!("{}d", days));
+        }
+        if hours != IBig::ZERO {
+            parts.push(format!("{}h", hours));
+        }
+        if minutes != IBig::ZERO {
+            parts.push(format!("{}m", minutes));
+        }
+        if fract != Decimal::ZERO {
+            parts.push(format!("{}s", Decimal::from(seconds).add(fract)));
+        } else if seconds != IBig::ZERO || (parts.is_empty() && self.pending.is_empty()) {
+            parts.push(format!("{}s", seconds));
+        }
+        if !self.pending.is_empty() {
+            parts.push(self.pending.clone());
+        }
+
+        write!(f, "{}{}", sign, parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod test_duration {
+    use super::*;
+
+    #[test]
+    fn formats_units() {
+        assert_eq!(
+            Duration::new(&dbig!(85), TimeUnit::Minutes).to_string(),
+            "1h 25m"
+        );
+        assert_eq!(
+            Duration::new(&dbig!(1.5), TimeUnit::Days).to_string(),
+            "1d 12h"
+        );
+        assert_eq!(Duration::from_seconds(dbig!(0)).to_string(), "0s");
+        assert_eq!(Duration::from_seconds(dbig!(-90)).to_string(), "-1m 30s");
+        assert_eq!(Duration::from_seconds(dbig!(61.25)).to_string(), "1m 1.25s");
+    }
+
+    #[test]
+    fn enters_several_units() {
+        let duration = Duration::new(&dbig!(1), TimeUnit::Hours).with_digit(2);
+        assert_eq!(duration.to_string(), "1h 2");
+
+        let duration = duration.with_digit(5);
+        assert_eq!(duration.seconds(), dbig!(5100));
+
+        let duration = duration.with_unit(TimeUnit::Minutes).unwrap();
+        assert_eq!(duration.to_string(), "1h 25m");
+        assert_eq!(duration.with_unit(TimeUnit::Seconds), None);
+    }
+
+    #[test]
+    fn parses_displayed_durations() {
+        for text in ["1h 25m", "1d 12h", "-1m 30s", "1m 1.25s", "0s", "1h 2"] {
+            assert_eq!(Duration::parse(text).unwrap().to_string(), text);
+        }
+
+        assert_eq!(Duration::parse("1h25m").unwrap().seconds(), dbig!(5100));
+        assert_eq!(Duration::parse("-1m 30s").unwrap().seconds(), dbig!(-90));
+        assert_eq!(Duration::parse("h"), None);
+        assert_eq!(Duration::parse("12"), None);
+        assert_eq!(Duration::parse("1x"), None);
+    }
+
+    #[test]
+    fn removes_pending_digits() {
+        let duration = Duration::new(&dbig!(1), TimeUnit::Hours).with_digit(2);
+
+        assert_eq!(duration.without_last().unwrap().to_string(), "1h");
+        assert_eq!(
+            Duration::new(&dbig!(1), TimeUnit::Hours).without_last(),
+            None
+        );
+    }
+
+    #[test]
+    fn calculates() {
+        let first = Duration::new(&dbig!(85), TimeUnit::Minutes);
+        let second = Duration::new(&dbig!(47), TimeUnit::Minutes);
+
+        assert_eq!(first.add(&second).to_string(), "2h 12m");
+        assert_eq!(first.sub(&second).to_string(), "38m");
+        assert_eq!(second.mul(&dbig!(2)).to_string(), "1h 34m");
+        assert_eq!(first.div(&dbig!(5)).unwrap().to_string(), "17m");
+        assert_eq!(first.div(&dbig!(0)), None);
+        assert_eq!(
+            Duration::new(&dbig!(1), TimeUnit::Hours)
+                .ratio(&Duration::new(&dbig!(15), TimeUnit::Minutes))
+                .unwrap(),
+            dbig!(4)
+        );
+    }
+}
@@ -0,0 +1,419 @@
+use crate::numeric_value::{precise, PRECISION};
+use crate::{calculation::Operation, complex};
+use anyhow::{anyhow, Result};
+use dashu::{base::Abs, Decimal};
+use std::{
+    fmt::Display,
+    iter::Peekable,
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::{Chars, FromStr},
+};
+
+const MAX_EXPONENT: usize = 10000;
+const PI: &str = "3.14159265358979323846264338327950";
+const E: &str = "2.71828182845904523536028747135266";
+
+/// A function that expressions can call, such as `sqrt(x)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function {
+    Sqrt,
+    Abs,
+    Ln,
+    Log,
+    Exp,
+    Sin,
+    Cos,
+    Tan,
+}
+
+/// A parsed expression in `x`, such as `x^2 + 2x`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(Decimal),
+    Variable,
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, Operation, Box<Expression>),
+    Power(Box<Expression>, Box<Expression>),
+    Call(Function, Box<Expression>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Decimal),
+    Name(String),
+    Operator(char),
+    Open,
+    Close,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Function> {
+        match name {
+            "sqrt" => Some(Function::Sqrt),
+            "abs" => Some(Function::Abs),
+            "ln" => Some(Function::Ln),
+            "log" => Some(Function::Log),
+            "exp" => Some(Function::Exp),
+            "sin" => Some(Function::Sin),
+            "cos" => Some(Function::Cos),
+            "tan" => Some(Function::Tan),
+            _ => None,
+        }
+    }
+
+    fn apply(&self, value: &Decimal) -> Option<Decimal> {
+        match self {
+            Function::Sqrt if *value < Decimal::ZERO => None,
+            Function::Sqrt => Some(complex::sqrt(value)),
+            Function::Abs => Some(value.clone().abs()),
+            Function::Ln | Function::Log if *value <= Decimal::ZERO => None,
+            Function::Ln => Some(precise(value).ln()),
+            Function::Log => Some(precise(value).ln().div(precise(&dbig!(10)).ln())),
+            Function::Exp if value.clone().abs() > Decimal::from(MAX_EXPONENT) => None,
+            Function::Exp => Some(precise(value).exp()),
+            Function::Sin => from_f64(to_f64(value).sin()),
+            Function::Cos => from_f64(to_f64(value).cos()),
+            Function::Tan => from_f64(to_f64(value).tan()),
+        }
+    }
+}
+
+impl Expression {
+    /// Parses an expression with numbers, `x`, `pi`, `e`, `+ - * / ^`, parentheses
+    /// and functions.
+    pub fn parse(text: &str) -> Result<Expression> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            position: 0,
+        };
+
+        let expression = parser.sum()?;
+
+        match parser.peek() {
+            None => Ok(expression),
+            Some(token) => Err(anyhow!("Unexpected {}", token)),
+        }
+    }
+
+    /// The value with `x` bound to the given number, or None when it is undefined.
+    pub fn evaluate(&self, x: &Decimal) -> Option<Decimal> {
+        let value = match self {
+            Expression::Number(value) => value.clone(),
+            Expression::Variable => x.clone(),
+            Expression::Negate(value) => value.evaluate(x)?.neg(),
+            Expression::Binary(left, operation, right) => {
+                let left = precise(&left.evaluate(x)?);
+                let right = right.evaluate(x)?;
+
+                match operation {
+                    Operation::Addition => left.add(right),
+                    Operation::Subtraction => left.sub(right),
+                    Operation::Multiplication => left.mul(right),
+                    Operation::Division if right == Decimal::ZERO => return None,
+                    Operation::Division => left.div(right),
+                    Operation::Equals => return None,
+                }
+            }
+            Expression::Power(base, exponent) => power(&base.evaluate(x)?, &exponent.evaluate(x)?)?,
+            Expression::Call(function, argument) => function.apply(&argument.evaluate(x)?)?,
+        };
+
+        Some(value.with_precision(PRECISION).value())
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(value) => write!(f, "{}", value),
+            Token::Name(name) => f.write_str(name),
+            Token::Operator(operator) => write!(f, "{}", operator),
+            Token::Open => f.write_str("("),
+            Token::Close => f.write_str(")"),
+        }
+    }
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+
+        token
+    }
+
+    fn sum(&mut self) -> Result<Expression> {
+        let mut expression = self.product()?;
+
+        while let Some(Token::Operator(operator @ ('+' | '-'))) = self.peek().cloned() {
+            self.next();
+
+            let operation = match operator {
+                '+' => Operation::Addition,
+                _ => Operation::Subtraction,
+            };
+            expression =
+                Expression::Binary(Box::new(expression), operation, Box::new(self.product()?));
+        }
+
+        Ok(expression)
+    }
+
+    fn product(&mut self) -> Result<Expression> {
+        let mut expression = self.unary()?;
+
+        loop {
+            let operation = match self.peek() {
+                Some(Token::Operator('*')) => Operation::Multiplication,
+                Some(Token::Operator('/')) => Operation::Division,
+                Some(Token::Number(_) | Token::Name(_) | Token::Open) => {
+                    expression = Expression::Binary(
+                        Box::new(expression),
+                        Operation::Multiplication,
+                        Box::new(self.power()?),
+                    );
+                    continue;
+                }
+                _ => return Ok(expression),
+            };
+
+            self.next();
+            expression =
+                Expression::Binary(Box::new(expression), operation, Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expression> {
+        match self.peek() {
+            Some(Token::Operator('-')) => {
+                self.next();
+                Ok(Expression::Negate(Box::new(self.unary()?)))
+            }
+            Some(Token::Operator('+')) => {
+                self.next();
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<Expression> {
+        let base = self.primary()?;
+
+        if let Some(Token::Operator('^')) = self.peek() {
+            self.next();
+            return Ok(Expression::Power(Box::new(base), Box::new(self.unary()?)));
+        }
+
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Expression> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Expression::Number(value)),
+            Some(Token::Open) => {
+                let expression = self.sum()?;
+
+                match self.next() {
+                    Some(Token::Close) => Ok(expression),
+                    _ => Err(anyhow!("Missing )")),
+                }
+            }
+            Some(Token::Name(name)) => match name.as_str() {
+                "x" => Ok(Expression::Variable),
+                "pi" | "π" => Ok(Expression::Number(Decimal::from_str(PI).unwrap())),
+                "e" => Ok(Expression::Number(Decimal::from_str(E).unwrap())),
+                _ => {
+                    let function = Function::from_name(&name)
+                        .ok_or_else(|| anyhow!("Unknown name {}", name))?;
+
+                    match self.next() {
+                        Some(Token::Open) => {}
+                        _ => return Err(anyhow!("Missing ( after {}", name)),
+                    }
+
+                    let argument = self.sum()?;
+
+                    match self.next() {
+                        Some(Token::Close) => Ok(Expression::Call(function, Box::new(argument))),
+                        _ => Err(anyhow!("Missing )")),
+                    }
+                }
+            },
+            Some(token) => Err(anyhow!("Unexpected {}", token)),
+            None => Err(anyhow!("Unexpected end of expression")),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+
+    while let Some(&char) = chars.peek() {
+        match char {
+            _ if char.is_whitespace() => {
+                chars.next();
+            }
+            '0'..='9' | '.' => tokens.push(Token::Number(number(&mut chars)?)),
+            _ if char.is_alphabetic() => {
+                let mut name = String::new();
+                while let Some(&char) = chars.peek().filter(|char| char.is_alphabetic()) {
+                    name.push(char.to_ascii_lowercase());
+                    chars.next();
+                }
+
+                // "2x" and "xsin(x)" read as products, so split a leading x off
+                // names that are not known functions.
+                if name.len() > 1 && name.starts_with('x') && Function::from_name(&name).is_none() {
+                    tokens.push(Token::Name("x".into()));
+                    name.remove(0);
+                }
+
+                tokens.push(Token::Name(name));
+            }
+            '+' | '^' => {
+                tokens.push(Token::Operator(char));
+                chars.next();
+            }
+            '-' | '−' => {
+                tokens.push(Token::Operator('-'));
+                chars.next();
+            }
+            '*' | '×' | '⨉' => {
+                tokens.push(Token::Operator('*'));
+                chars.next();
+            }
+            '/' | '÷' => {
+                tokens.push(Token::Operator('/'));
+                chars.next();
+            }
+            '(' => {
+                tokens.push(Token::Open);
+                chars.next();
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                chars.next();
+            }
+            _ => return Err(anyhow!("Unexpected {}", char)),
+        }
+    }
+
+    if tokens.is_empty() {
+        return Err(anyhow!("Enter a function of x"));
+    }
+
+    Ok(tokens)
+}
+
+fn number(chars: &mut Peekable<Chars>) -> Result<Decimal> {
+    let mut text = String::new();
+    while let Some(&char) = chars
+        .peek()
+        .filter(|char| char.is_ascii_digit() || **char == '.')
+    {
+        text.push(char);
+        chars.next();
+    }
+
+    Decimal::from_str(&text).map_err(|err| anyhow!("Invalid number {}: {}", text, err))
+}
+
+fn power(base: &Decimal, exponent: &Decimal) -> Option<Decimal> {
+    if exponent.clone().abs() > Decimal::from(MAX_EXPONENT) {
+        return None;
+    }
+
+    if exponent.fract() == Decimal::ZERO {
+        if *base == Decimal::ZERO && *exponent < Decimal::ZERO {
+            return None;
+        }
+
+        return Some(precise(base).powi(exponent.to_int().value()));
+    }
+
+    if *base <= Decimal::ZERO {
+        return None;
+    }
+
+    Some(precise(base).powf(exponent))
+}
+
+/// Goes through the decimal text, as dashu's base conversion can trip over long
+/// significands.
+pub fn to_f64(value: &Decimal) -> f64 {
+    value.to_string().parse().unwrap_or(f64::NAN)
+}
+
+fn from_f64(value: f64) -> Option<Decimal> {
+    if !value.is_finite() {
+        return None;
+    }
+
+    Decimal::from_str(&value.to_string()).ok()
+}
+
+#[cfg(test)]
+mod test_expression {
+    use super::*;
+
+    fn evaluate(text: &str, x: Decimal) -> Option<Decimal> {
+        Expression::parse(text).unwrap().evaluate(&x)
+    }
+
+    #[test]
+    fn respects_precedence() {
+        assert_eq!(evaluate("1 + 2 * 3", dbig!(0)), Some(dbig!(7)));
+        assert_eq!(evaluate("(1 + 2) * 3", dbig!(0)), Some(dbig!(9)));
+        assert_eq!(evaluate("2^3^2", dbig!(0)), Some(dbig!(512)));
+        assert_eq!(evaluate("-x^2", dbig!(3)), Some(dbig!(-9)));
+        assert_eq!(evaluate("10 - 4 - 3", dbig!(0)), Some(dbig!(3)));
+    }
+
+    #[test]
+    fn multiplies_implicitly() {
+        assert_eq!(evaluate("2x", dbig!(4)), Some(dbig!(8)));
+        assert_eq!(evaluate("3x^2 - 2x + 1", dbig!(2)), Some(dbig!(9)));
+        assert_eq!(evaluate("2(x + 1)", dbig!(1)), Some(dbig!(4)));
+        assert_eq!(evaluate("x sqrt(x)", dbig!(4)), Some(dbig!(8)));
+    }
+
+    #[test]
+    fn evaluates_functions() {
+        assert_eq!(evaluate("sqrt(x)", dbig!(16)), Some(dbig!(4)));
+        assert_eq!(evaluate("abs(x)", dbig!(-2.5)), Some(dbig!(2.5)));
+        assert_eq!(evaluate("sin(0)", dbig!(0)), Some(dbig!(0)));
+        assert_eq!(evaluate("x^-1", dbig!(4)), Some(dbig!(0.25)));
+        assert_eq!(
+            evaluate("ln(e)", dbig!(0)).map(|value| to_f64(&value).round()),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn leaves_undefined_points_empty() {
+        assert_eq!(evaluate("1/x", dbig!(0)), None);
+        assert_eq!(evaluate("sqrt(x)", dbig!(-1)), None);
+        assert_eq!(evaluate("ln(x)", dbig!(0)), None);
+        assert_eq!(evaluate("x^0.5", dbig!(-4)), None);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(Expression::parse("").is_err());
+        assert!(Expression::parse("2 +").is_err());
+        assert!(Expression::parse("(x + 1").is_err());
+        assert!(Expression::parse("foo(x)").is_err());
+        assert!(Expression::parse("x $ 2").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+//! Results pinned for every front end, so calc-core and calc-wasm stay in
+//! step. Not part of the semver API.
+
+use crate::calculation::{Calculation, Operation};
+
+const RESULTS: &str = include_str!("../fixtures/results.tsv");
+
+/// The pinned keypad entries from `fixtures/results.tsv`, each with the
+/// result the display shows.
+pub fn results() -> impl Iterator<Item = (&'static str, &'static str)> {
+    RESULTS
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('\t'))
+}
+
+/// Presses `keys` on a new calculation, or returns `None` for a key other than
+/// a digit, `.`, `+`, `-`, `*`, `/` or `=`.
+pub fn replay(keys: &str) -> Option<Calculation> {
+    let mut calculation = Calculation::default();
+
+    for key in keys.chars() {
+        match key {
+            '+' => calculation.append_operation(Operation::Addition),
+            '-' => calculation.append_operation(Operation::Subtraction),
+            '*' => calculation.append_operation(Operation::Multiplication),
+            '/' => calculation.append_operation(Operation::Division),
+            '.' => calculation.add_comma(),
+            '=' => calculation.calculate(),
+            digit => calculation.append_number(digit.to_digit(10)? as usize),
+        }
+    }
+
+    Some(calculation)
+}
+
+#[cfg(test)]
+mod test_fixtures {
+    use super::*;
+
+    #[test]
+    fn matches_pinned_results() {
+        assert!(results().count() > 0);
+
+        for (keys, result) in results() {
+            assert_eq!(
+                replay(keys).unwrap().current_operation_string(),
+                result,
+                "{}",
+                keys
+            );
+            assert_eq!(
+                Calculation::parse(keys).unwrap().current_operation_string(),
+                result,
+                "{}",
+                keys
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(replay("2+x=").is_none());
+    }
+}
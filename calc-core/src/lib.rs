@@ -0,0 +1,26 @@
+//! The calculation engine of the hello-gpui calculator, without any GUI
+//! dependencies: the entry line with its operands and operations, exact,
+//! complex and duration values, and expressions in `x`.
+//!
+//! The public API follows semver. `Operation` and `OperandValue` are
+//! `#[non_exhaustive]`, so new variants are not breaking changes.
+
+#[macro_use]
+extern crate dashu_macros;
+
+#[macro_use]
+extern crate serde_derive;
+
+#[macro_use]
+extern crate schemars;
+
+pub mod calculation;
+pub mod complex;
+pub mod duration;
+pub mod expression;
+#[doc(hidden)]
+pub mod fixtures;
+pub mod numeric_value;
+
+pub use calculation::{Calculation, Operand, OperandValue, Operation};
+pub use numeric_value::NumericValue;
@@ -0,0 +1,293 @@
+use dashu::{
+    base::{BitTest, UnsignedAbs},
+    integer::{IBig, UBig},
+    rational::RBig,
+    Decimal,
+};
+use std::{
+    fmt::Display,
+    ops::{Div, Mul},
+};
+
+/// Significant digits kept by intermediate results throughout the engine.
+pub const PRECISION: usize = 30;
+
+/// A number as typed or shown: a decimal, whether its decimal separator was
+/// typed, and an exact fraction when there is one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumericValue {
+    value: Decimal,
+    comma: bool,
+    fraction: Option<Fraction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Fraction {
+    exact: RBig,
+    entry: Option<FractionEntry>,
+    shown: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FractionEntry {
+    whole: Option<IBig>,
+    numerator: IBig,
+    denominator: Option<UBig>,
+}
+
+impl NumericValue {
+    /// A plain decimal number.
+    pub fn new(value: Decimal) -> Self {
+        NumericValue {
+            value,
+            comma: false,
+            fraction: None,
+        }
+    }
+
+    /// A number whose decimal separator was typed without decimals yet, shown as `2.`.
+    pub fn new_with_comma(value: Decimal) -> Self {
+        NumericValue {
+            value,
+            comma: true,
+            fraction: None,
+        }
+    }
+
+    /// An exact rational number, shown as a fraction.
+    pub fn exact(exact: RBig) -> Self {
+        NumericValue {
+            value: to_decimal(&exact),
+            comma: false,
+            fraction: Some(Fraction {
+                exact,
+                entry: None,
+                shown: true,
+            }),
+        }
+    }
+
+    /// Whether the number has digits after the decimal point.
+    pub fn is_float(&self) -> bool {
+        self.value.fract() != Decimal::ZERO
+    }
+
+    /// The decimal value, rounded for exact numbers.
+    pub fn val(&self) -> &Decimal {
+        &self.value
+    }
+
+    /// Whether the decimal separator was typed.
+    pub fn has_comma(&self) -> bool {
+        self.comma
+    }
+
+    /// Whether the number is an exact fraction.
+    pub fn is_exact(&self) -> bool {
+        self.fraction.is_some()
+    }
+
+    /// The exact value, converted from the decimal for plain numbers.
+    pub fn to_exact(&self) -> Option<RBig> {
+        match &self.fraction {
+            Some(fraction) => Some(fraction.exact.clone()),
+            None => RBig::try_from(self.value.clone()).ok(),
+        }
+    }
+
+    /// Switches an exact number between fraction and decimal form.
+    pub fn toggle_fraction(&mut self) {
+        if let Some(fraction) = self.fraction.as_mut() {
+            fraction.shown = !fraction.shown;
+        }
+    }
+
+    /// Starts typing a denominator, or None when the number cannot be a numerator.
+    pub fn with_fraction_bar(&self) -> Option<Self> {
+        let entry = match self.fraction.as_ref().map(|fraction| &fraction.entry) {
+            Some(Some(FractionEntry {
+                whole: None,
+                numerator,
+                denominator: Some(denominator),
+            })) => FractionEntry {
+                whole: Some(numerator.clone()),
+                numerator: denominator.clone().into(),
+                denominator: None,
+            },
+            Some(_) => return None,
+            None => {
+                if self.comma || self.is_float() {
+                    return None;
+                }
+
+                FractionEntry {
+                    whole: None,
+                    numerator: self.value.trunc().to_int().value(),
+                    denominator: None,
+                }
+            }
+        };
+
+        Some(Self::from_entry(entry))
+    }
+
+    /// Types a digit into the denominator being entered, or None when there is none.
+    pub fn with_fraction_digit(&self, num: usize) -> Option<Self> {
+        let mut entry = self.fraction.as_ref()?.entry.clone()?;
+
+        entry.denominator = match entry.denominator {
+            None if num == 0 => None,
+            None => Some(UBig::from(num)),
+            Some(denominator) => Some(denominator * UBig::from(10u8) + UBig::from(num)),
+        };
+
+        Some(Self::from_entry(entry))
+    }
+
+    fn from_entry(entry: FractionEntry) -> Self {
+        let denominator = entry.denominator.clone().unwrap_or(UBig::ONE);
+        let fraction = RBig::from_parts(entry.numerator.clone(), denominator);
+
+        let exact = match &entry.whole {
+            Some(whole) if *whole < IBig::ZERO => RBig::from(whole.clone()) - fraction,
+            Some(whole) => RBig::from(whole.clone()) + fraction,
+            None => fraction,
+        };
+
+        NumericValue {
+            value: to_decimal(&exact),
+            comma: false,
+            fraction: Some(Fraction {
+                exact,
+                entry: Some(entry),
+                shown: true,
+            }),
+        }
+    }
+
+    // The bytes the digits of the value and its fraction take on the heap.
+    pub(crate) fn heap_size(&self) -> usize {
+        let fraction = self.fraction.as_ref().map_or(0, |fraction| {
+            let entry = fraction.entry.as_ref().map_or(0, |entry| {
+                entry.whole.as_ref().map_or(0, int_heap_size)
+                    + int_heap_size(&entry.numerator)
+                    + entry.denominator.as_ref().map_or(0, int_heap_size)
+            });
+
+            int_heap_size(fraction.exact.numerator())
+                + int_heap_size(fraction.exact.denominator())
+                + entry
+        });
+
+        decimal_heap_size(&self.value) + fraction
+    }
+}
+
+// Digits are stored as whole bytes, so this rounds the bit length up.
+pub(crate) fn int_heap_size(value: &impl BitTest) -> usize {
+    value.bit_len().div_ceil(8)
+}
+
+pub(crate) fn decimal_heap_size(value: &Decimal) -> usize {
+    int_heap_size(value.repr().significand())
+}
+
+/// `value` widened to [`PRECISION`] digits, so arithmetic on it is not limited
+/// to the digits of its literal.
+pub fn precise(value: &Decimal) -> Decimal {
+    value.clone().with_precision(PRECISION).value()
+}
+
+/// `value` rounded half away from zero to `places` decimal places.
+pub fn round_to_places(value: &Decimal, places: usize) -> Decimal {
+    let scale = Decimal::from(10u8).powi(places.into());
+
+    precise(value).mul(&scale).round().div(&scale)
+}
+
+fn to_decimal(exact: &RBig) -> Decimal {
+    exact.to_float(PRECISION).value()
+}
+
+fn write_fraction(f: &mut std::fmt::Formatter<'_>, exact: &RBig) -> std::fmt::Result {
+    let numerator = exact.numerator();
+    let denominator = exact.denominator();
+
+    if *denominator == UBig::ONE {
+        return write!(f, "{}", numerator);
+    }
+
+    let magnitude = numerator.unsigned_abs();
+    if magnitude < *denominator {
+        return write!(f, "{}/{}", numerator, denominator);
+    }
+
+    let sign = if *numerator < IBig::ZERO { "-" } else { "" };
+
+    write!(
+        f,
+        "{}{} {}/{}",
+        sign,
+        &magnitude / denominator,
+        &magnitude % denominator,
+        denominator
+    )
+}
+
+impl Display for FractionEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(whole) = &self.whole {
+            write!(f, "{} ", whole)?;
+        }
+
+        write!(f, "{}/", self.numerator)?;
+
+        match &self.denominator {
+            Some(denominator) => write!(f, "{}", denominator),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for NumericValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(fraction) = &self.fraction {
+            if let Some(entry) = &fraction.entry {
+                return write!(f, "{}", entry);
+            }
+
+            if fraction.shown {
+                return write_fraction(f, &fraction.exact);
+            }
+        }
+
+        if self.comma && !self.is_float() {
+            return write!(f, "{}.", self.value);
+        }
+
+        write!(f, "{}", self.value)
+    }
+}
+
+impl Default for NumericValue {
+    fn default() -> Self {
+        NumericValue {
+            value: Decimal::ZERO,
+            comma: false,
+            fraction: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_round_to_places {
+    use super::*;
+
+    #[test]
+    fn rounds_half_away_from_zero() {
+        assert_eq!(round_to_places(&dbig!(10.005), 2), dbig!(10.01));
+        assert_eq!(round_to_places(&dbig!(-10.005), 2), dbig!(-10.01));
+        assert_eq!(round_to_places(&dbig!(3.14159), 2), dbig!(3.14));
+        assert_eq!(round_to_places(&dbig!(2.5), 0), dbig!(3));
+    }
+}
@@ -0,0 +1,138 @@
+use calc_core::{expression::Expression, Calculation, NumericValue};
+use dashu::Decimal;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+// An entry line, shown and calculated exactly as in the desktop app.
+#[wasm_bindgen]
+pub struct Entry(Calculation);
+
+#[wasm_bindgen]
+impl Entry {
+    pub fn current(&self) -> String {
+        self.0.current_operation_string()
+    }
+
+    pub fn past(&self) -> String {
+        self.0.past_operations_string()
+    }
+
+    pub fn preview(&self) -> Option<String> {
+        self.0.preview().map(|value| value.to_string())
+    }
+
+    pub fn calculate(&mut self) {
+        self.0.calculate();
+    }
+}
+
+#[wasm_bindgen]
+pub fn parse(text: &str) -> Result<Entry, JsError> {
+    entry(text).map(Entry).map_err(|err| JsError::new(&err))
+}
+
+// Calculates an entry line such as `2+3*4` and returns the result as shown.
+#[wasm_bindgen]
+pub fn evaluate(text: &str) -> Result<String, JsError> {
+    calculate(text).map_err(|err| JsError::new(&err))
+}
+
+// Evaluates an expression in `x`, like `hello-gpui -e`.
+#[wasm_bindgen(js_name = evaluateExpression)]
+pub fn evaluate_expression(text: &str, x: &str) -> Result<String, JsError> {
+    evaluate_in(text, x).map_err(|err| JsError::new(&err))
+}
+
+// Formats a number as the display shows a result.
+#[wasm_bindgen]
+pub fn format(value: &str) -> Result<String, JsError> {
+    format_value(value).map_err(|err| JsError::new(&err))
+}
+
+// JsError can only be created on wasm targets, so the logic reports plain
+// strings and stays testable natively.
+fn entry(text: &str) -> Result<Calculation, String> {
+    Calculation::parse(text).ok_or_else(|| format!("Invalid entry {}", text))
+}
+
+fn calculate(text: &str) -> Result<String, String> {
+    let mut calculation = entry(text)?;
+    // Parsing already calculated an entry ending in `=`.
+    if calculation.past_operands().is_empty() {
+        calculation.calculate();
+    }
+
+    Ok(calculation.current_operation_string())
+}
+
+fn evaluate_in(text: &str, x: &str) -> Result<String, String> {
+    let expression = Expression::parse(text).map_err(|err| err.to_string())?;
+    let value = expression
+        .evaluate(&decimal(x)?)
+        .ok_or_else(|| format!("{} is undefined", text))?;
+
+    Ok(NumericValue::new(value).to_string())
+}
+
+fn format_value(value: &str) -> Result<String, String> {
+    Ok(NumericValue::new(decimal(value)?).to_string())
+}
+
+fn decimal(value: &str) -> Result<Decimal, String> {
+    Decimal::from_str(value.trim()).map_err(|err| format!("Invalid number {}: {}", value, err))
+}
+
+#[cfg(test)]
+mod test_wasm {
+    use super::*;
+    use calc_core::fixtures;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn matches_pinned_results() {
+        for (keys, result) in fixtures::results() {
+            assert_eq!(calculate(keys).unwrap(), result, "{}", keys);
+            assert_eq!(
+                calculate(keys.trim_end_matches('=')).unwrap(),
+                result,
+                "{}",
+                keys
+            );
+        }
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn exposes_entry_lines() {
+        let mut entry = Entry(entry("12 * 3").unwrap());
+
+        assert_eq!(entry.current(), "12⨉3");
+        assert_eq!(entry.preview().as_deref(), Some("36"));
+
+        entry.calculate();
+        assert_eq!(entry.current(), "36");
+        assert_eq!(entry.past(), "12⨉3");
+        assert!(calculate("12+x").is_err());
+        assert_eq!(calculate("5 + 3 =").unwrap(), "8");
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn evaluates_expressions() {
+        assert_eq!(evaluate_in("x * 2 + 1", "20").unwrap(), "41");
+        assert_eq!(
+            evaluate_in("x / 3", "1").unwrap(),
+            "0.333333333333333333333333333333"
+        );
+        assert!(evaluate_in("1 / x", "0").is_err());
+        assert!(evaluate_in("x", "one").is_err());
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn formats_like_the_display() {
+        assert_eq!(format_value(" 2.50 ").unwrap(), "2.5");
+        assert_eq!(format_value("-12").unwrap(), "-12");
+        assert_eq!(format_value("0.250").unwrap(), "0.25");
+        assert!(format_value("1,5").is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use gpui::{div, prelude::*, px, rgb, Context, ElementId, Rgba, SharedString};
+
+// The small text button the panes use for their actions, such as Paste or
+// Solve. It is dark gray unless given another color.
+pub fn action_button<V: 'static>(
+    id: impl Into<ElementId>,
+    label: impl Into<SharedString>,
+    bg: Option<Rgba>,
+    cx: &mut Context<V>,
+    handler: impl Fn(&mut V, &mut Context<V>) + 'static,
+) -> impl IntoElement {
+    div()
+        .id(id)
+        .px(px(3.))
+        .rounded_sm()
+        .bg(bg.unwrap_or_else(|| rgb(0x515251)))
+        .child(label.into())
+        .on_click(cx.listener(move |this, _, _, cx| handler(this, cx)))
+}
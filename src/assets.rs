@@ -1,37 +1,184 @@
 use anyhow::Result;
 use gpui::{AssetSource, SharedString};
-use std::fs;
-use std::path::PathBuf;
+use rust_embed::RustEmbed;
+use std::{
+    borrow::Cow,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
 
+#[derive(RustEmbed)]
+#[folder = "icons/"]
+struct EmbeddedIcons;
+
+// Icons are built into the binary; files in the override directory (when set)
+// take precedence so themes can replace or add icons without a rebuild.
 pub struct Assets {
-    base: PathBuf,
+    overrides: Option<PathBuf>,
 }
 
 impl Assets {
-    pub fn new(base: PathBuf) -> Self {
-        Self { base }
+    pub fn new(overrides: Option<PathBuf>) -> Self {
+        Self { overrides }
+    }
+
+    fn read_override(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let Some(base) = &self.overrides else {
+            return Ok(None);
+        };
+
+        match fs::read(base.join(path)) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if matches!(err.kind(), ErrorKind::NotFound | ErrorKind::IsADirectory) => {
+                Ok(None)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    // Lists files below `path` recursively, like the embedded set.
+    fn list_overrides(&self, path: &str) -> Result<Vec<String>> {
+        let mut files = vec![];
+
+        if let Some(base) = &self.overrides {
+            walk(&base.join(path), &directory(path), &mut files)?;
+        }
+
+        Ok(files)
     }
 }
 
 impl AssetSource for Assets {
-    fn load(&self, path: &str) -> Result<Option<std::borrow::Cow<'static, [u8]>>> {
-        fs::read(self.base.join(path))
-            .map(|data| Some(std::borrow::Cow::Owned(data)))
-            .map_err(|err| err.into())
+    fn load(&self, path: &str) -> Result<Option<Cow<'static, [u8]>>> {
+        if let Some(data) = self.read_override(path)? {
+            return Ok(Some(Cow::Owned(data)));
+        }
+
+        Ok(EmbeddedIcons::get(path).map(|file| file.data))
     }
 
     fn list(&self, path: &str) -> Result<Vec<SharedString>> {
-        fs::read_dir(self.base.join(path))
-            .map(|entries| {
-                entries
-                    .filter_map(|entry| {
-                        entry
-                            .ok()
-                            .and_then(|entry| entry.file_name().into_string().ok())
-                            .map(SharedString::from)
-                    })
-                    .collect()
-            })
-            .map_err(|err| err.into())
+        let prefix = directory(path);
+        let mut paths: Vec<String> = EmbeddedIcons::iter()
+            .filter(|file| file.starts_with(&prefix))
+            .map(String::from)
+            .collect();
+
+        paths.extend(self.list_overrides(path)?);
+        paths.sort();
+        paths.dedup();
+
+        Ok(paths.into_iter().map(SharedString::from).collect())
+    }
+}
+
+fn walk(dir: &Path, prefix: &str, files: &mut Vec<String>) -> Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+
+    for entry in entries.flatten() {
+        let (Ok(name), Ok(file_type)) = (entry.file_name().into_string(), entry.file_type()) else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            walk(&entry.path(), &format!("{}{}/", prefix, name), files)?;
+        } else if file_type.is_file() {
+            files.push(format!("{}{}", prefix, name));
+        }
+    }
+
+    Ok(())
+}
+
+fn directory(path: &str) -> String {
+    match path.trim_end_matches('/') {
+        "" => String::new(),
+        path => format!("{}/", path),
+    }
+}
+
+#[cfg(test)]
+mod test_assets {
+    use super::*;
+
+    fn override_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("hello-gpui-test-icons-{}", name));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn loads_embedded_icons() {
+        let assets = Assets::new(None);
+        let icon = assets.load("undo.svg").unwrap().unwrap();
+
+        assert!(String::from_utf8_lossy(&icon).contains("<svg"));
+        assert!(assets.load("missing.svg").unwrap().is_none());
+    }
+
+    #[test]
+    fn lists_embedded_icons() {
+        let assets = Assets::new(None);
+
+        assert_eq!(
+            assets.list("").unwrap(),
+            vec![
+                SharedString::from("pack.json"),
+                "rocket.svg".into(),
+                "sqrt.svg".into(),
+                "undo.svg".into()
+            ]
+        );
+        assert!(assets.list("themes").unwrap().is_empty());
+    }
+
+    #[test]
+    fn prefers_override_files() {
+        let base = override_dir("load");
+        fs::write(base.join("undo.svg"), "<svg>custom</svg>").unwrap();
+        let assets = Assets::new(Some(base));
+
+        assert_eq!(
+            assets.load("undo.svg").unwrap().unwrap().as_ref(),
+            b"<svg>custom</svg>"
+        );
+        assert!(assets.load("rocket.svg").unwrap().is_some());
+        assert!(assets.load("missing.svg").unwrap().is_none());
+    }
+
+    #[test]
+    fn lists_both_sources() {
+        let base = override_dir("list");
+        fs::write(base.join("undo.svg"), "<svg/>").unwrap();
+        fs::write(base.join("extra.svg"), "<svg/>").unwrap();
+        fs::create_dir_all(base.join("theme")).unwrap();
+        fs::write(base.join("theme").join("dark.svg"), "<svg/>").unwrap();
+        let assets = Assets::new(Some(base));
+
+        let listed = assets.list("").unwrap();
+        assert!(listed.contains(&"extra.svg".into()));
+        assert!(listed.contains(&"rocket.svg".into()));
+        assert!(listed.contains(&"theme/dark.svg".into()));
+        assert_eq!(listed.iter().filter(|path| *path == "undo.svg").count(), 1);
+        assert_eq!(
+            assets.list("theme/").unwrap(),
+            vec![SharedString::from("theme/dark.svg")]
+        );
+        assert!(assets.list("missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn ignores_missing_override_directory() {
+        let assets = Assets::new(Some(std::env::temp_dir().join("hello-gpui-no-such-icons")));
+
+        assert!(assets.load("rocket.svg").unwrap().is_some());
+        assert_eq!(assets.list("").unwrap().len(), 4);
     }
 }
@@ -0,0 +1,171 @@
+use super::{action::FocusDirection, calculation::Calculation};
+use gpui::SharedString;
+
+#[derive(Clone)]
+pub enum ButtonLabel {
+    String(SharedString),
+    // A semantic name resolved through the active icon pack.
+    Icon(SharedString),
+}
+
+impl ButtonLabel {
+    pub fn accessible_name(&self) -> SharedString {
+        match self {
+            ButtonLabel::String(str) => match str.as_ref() {
+                "AC" => "all clear".into(),
+                "±" => "plus minus".into(),
+                "%" => "percent".into(),
+                "÷" => "divide".into(),
+                "⨉" => "multiply".into(),
+                "－" | "-" => "minus".into(),
+                "＋" | "+" => "plus".into(),
+                "＝" => "equals".into(),
+                "," => "decimal point".into(),
+                "i" => "imaginary".into(),
+                "∠" => "polar form".into(),
+                "a/b" => "fraction bar".into(),
+                "0.5" => "fraction to decimal".into(),
+                "ab/c" => "exact fractions".into(),
+                "↶" => "undo".into(),
+                "↷" => "redo".into(),
+                _ => str.clone(),
+            },
+            ButtonLabel::Icon(name) => match name.as_ref() {
+                "mode" => "next mode".into(),
+                "sqrt" => "square root".into(),
+                name => name.replace(['-', '_'], " ").into(),
+            },
+        }
+    }
+}
+
+// Tracks the result last read out so every change to it is announced once,
+// whether it came from a key, paste, function, caret edit or RPC request.
+#[derive(Debug)]
+pub struct Announcer {
+    current: String,
+    past: String,
+}
+
+impl Announcer {
+    pub fn new(calculation: &Calculation) -> Self {
+        Announcer {
+            current: calculation.current_operation_string(),
+            past: calculation.past_operations_string(),
+        }
+    }
+
+    // Returns what to announce when the displayed result has changed. A new
+    // past operation is read out with its result.
+    pub fn announce(&mut self, calculation: &Calculation) -> Option<String> {
+        let current = calculation.current_operation_string();
+        let past = calculation.past_operations_string();
+
+        if current == self.current && past == self.past {
+            return None;
+        }
+
+        let announcement = if past != self.past && !past.is_empty() {
+            format!(
+                "{} equals {}",
+                calculation.past_operations_spoken(),
+                current
+            )
+        } else {
+            current.clone()
+        };
+
+        self.current = current;
+        self.past = past;
+
+        Some(announcement)
+    }
+}
+
+// The index of the button focused after moving in `direction` across a keypad
+// of `len` buttons laid out in rows of `columns`. Moving wraps around.
+pub fn focus_target(
+    focused: Option<usize>,
+    direction: &FocusDirection,
+    len: usize,
+    columns: usize,
+) -> usize {
+    match (focused, direction) {
+        (None, FocusDirection::Previous) => len - 1,
+        (None, _) => 0,
+        (Some(i), FocusDirection::Next) => (i + 1) % len,
+        (Some(i), FocusDirection::Previous) => (i + len - 1) % len,
+        (Some(i), FocusDirection::Up) => (i + len - columns) % len,
+        (Some(i), FocusDirection::Down) => (i + columns) % len,
+        (Some(i), FocusDirection::Left) => i - i % columns + (i + columns - 1) % columns,
+        (Some(i), FocusDirection::Right) => i - i % columns + (i + 1) % columns,
+    }
+}
+
+#[cfg(test)]
+mod test_accessibility {
+    use super::*;
+    use crate::calculator::calculation::Operation;
+
+    fn name(label: ButtonLabel) -> String {
+        label.accessible_name().to_string()
+    }
+
+    #[test]
+    fn names_symbols_and_icons() {
+        assert_eq!(name(ButtonLabel::String("AC".into())), "all clear");
+        assert_eq!(name(ButtonLabel::String("⨉".into())), "multiply");
+        assert_eq!(name(ButtonLabel::String("－".into())), "minus");
+        assert_eq!(name(ButtonLabel::String("7".into())), "7");
+        assert_eq!(name(ButtonLabel::String("a/b".into())), "fraction bar");
+        assert_eq!(name(ButtonLabel::String("↶".into())), "undo");
+        assert_eq!(name(ButtonLabel::Icon("mode".into())), "next mode");
+        assert_eq!(name(ButtonLabel::Icon("sqrt".into())), "square root");
+        assert_eq!(name(ButtonLabel::Icon("square_root".into())), "square root");
+        assert_eq!(name(ButtonLabel::Icon("back-space".into())), "back space");
+    }
+
+    #[test]
+    fn moves_focus_in_order() {
+        assert_eq!(focus_target(None, &FocusDirection::Next, 20, 4), 0);
+        assert_eq!(focus_target(None, &FocusDirection::Previous, 20, 4), 19);
+        assert_eq!(focus_target(Some(5), &FocusDirection::Next, 20, 4), 6);
+        assert_eq!(focus_target(Some(19), &FocusDirection::Next, 20, 4), 0);
+        assert_eq!(focus_target(Some(0), &FocusDirection::Previous, 20, 4), 19);
+    }
+
+    #[test]
+    fn moves_focus_across_the_grid() {
+        assert_eq!(focus_target(None, &FocusDirection::Down, 16, 4), 0);
+        assert_eq!(focus_target(Some(5), &FocusDirection::Up, 16, 4), 1);
+        assert_eq!(focus_target(Some(1), &FocusDirection::Up, 16, 4), 13);
+        assert_eq!(focus_target(Some(13), &FocusDirection::Down, 16, 4), 1);
+        assert_eq!(focus_target(Some(4), &FocusDirection::Left, 16, 4), 7);
+        assert_eq!(focus_target(Some(7), &FocusDirection::Right, 16, 4), 4);
+        assert_eq!(focus_target(Some(6), &FocusDirection::Right, 16, 4), 7);
+    }
+
+    #[test]
+    fn announces_each_result_change_once() {
+        let mut calculation = Calculation::default();
+        let mut announcer = Announcer::new(&calculation);
+        assert_eq!(announcer.announce(&calculation), None);
+
+        calculation.append_number(2);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_number(3);
+        assert_eq!(announcer.announce(&calculation).as_deref(), Some("2+3"));
+        assert_eq!(announcer.announce(&calculation), None);
+
+        calculation.calculate();
+        assert_eq!(
+            announcer.announce(&calculation).as_deref(),
+            Some("2 plus 3 equals 5")
+        );
+
+        // Values entered without a key, such as a paste, are announced too.
+        calculation
+            .replace_current_operand(Calculation::parse("42").unwrap().current_operand_value());
+        assert_eq!(announcer.announce(&calculation).as_deref(), Some("42"));
+    }
+}
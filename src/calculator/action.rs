@@ -0,0 +1,44 @@
+use super::{calculation::Operation, caret::CaretMotion, duration::TimeUnit};
+use gpui::impl_actions;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum FocusDirection {
+    Next,
+    Previous,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum CalculatorAction {
+    Numeric(usize),
+    Op(Operation),
+    Calculate,
+    Backspace,
+    Delete,
+    Comma,
+    Focus(FocusDirection),
+    Activate,
+    Unfocus,
+    NextMode,
+    FractionBar,
+    ToggleFraction,
+    ToggleExact,
+    Imaginary,
+    SquareRoot,
+    TogglePolar,
+    AddDataPoint,
+    Undo,
+    Redo,
+    Paste,
+    TimeUnit(TimeUnit),
+    ToggleRecording,
+    RunMacro(usize),
+    MoveCaret(CaretMotion),
+    SelectCaret(CaretMotion),
+    NoAction,
+}
+
+impl_actions!(calculator, [CalculatorAction]);
@@ -1,24 +1,20 @@
-use crate::round_button::RoundButton;
+use crate::{icon_pack::IconPack, round_button::RoundButton};
 use gpui::{
-    div, prelude::*, rgb, rgba, svg, ClickEvent, Context, EventEmitter, Rgba, SharedString, Timer,
-    Window,
+    div, prelude::*, px, rgb, rgba, svg, App, ClickEvent, Context, Div, EventEmitter, Rgba,
+    SharedString, Timer, Window,
 };
 use std::time::Duration;
 
-use super::calculation::Operation;
+use super::{accessibility::ButtonLabel, calculation::Operation};
 
 const DELAY: Duration = Duration::from_millis(50);
 
-#[derive(Clone)]
-pub enum ButtonLabel {
-    String(SharedString),
-    Svg(String),
-}
-
 pub struct Button {
     label: ButtonLabel,
     color: Rgba,
     is_active: bool,
+    is_focused: bool,
+    size: f32,
     event_to_emit: Event,
 }
 
@@ -28,6 +24,8 @@ impl Button {
             label,
             color,
             is_active: false,
+            is_focused: false,
+            size: 42.,
             event_to_emit,
         }
     }
@@ -49,6 +47,21 @@ impl Button {
         cx.notify();
     }
 
+    pub fn set_focused(&mut self, is_focused: bool, cx: &mut Context<Self>) {
+        self.is_focused = is_focused;
+        cx.notify();
+    }
+
+    pub fn activate(&mut self, cx: &mut Context<Self>) {
+        self.set_clicked(cx);
+        cx.emit(self.event_to_emit.clone());
+        cx.notify();
+    }
+
+    pub fn accessible_name(&self) -> SharedString {
+        self.label.accessible_name()
+    }
+
     fn handle_click(&mut self, _event: &ClickEvent, cx: &mut Context<Self>) {
         cx.emit(self.event_to_emit.clone());
     }
@@ -57,11 +70,29 @@ impl Button {
         self.label = label;
     }
 
-    pub fn render_label(&mut self) -> impl IntoElement {
-        match &self.label {
-            ButtonLabel::String(str) => div().child(str.clone()),
-            ButtonLabel::Svg(path) => {
-                div().child(svg().path(path).mt_1().size_4().text_color(rgb(0xffffff)))
+    pub fn set_size(&mut self, size: f32) {
+        self.size = size;
+    }
+
+    pub fn render_label(&mut self, cx: &App) -> impl IntoElement {
+        render_label(&self.label, cx)
+    }
+}
+
+// Shared with the round buttons of the side panes.
+pub fn render_label(label: &ButtonLabel, cx: &App) -> Div {
+    match label {
+        ButtonLabel::String(str) => div().child(str.clone()),
+        ButtonLabel::Icon(name) => {
+            match cx.try_global::<IconPack>().and_then(|pack| pack.icon(name)) {
+                Some(icon) => div().child(
+                    svg()
+                        .path(icon.path.clone())
+                        .mt_1()
+                        .size(px(icon.size))
+                        .text_color(rgb(icon.tint)),
+                ),
+                None => div().child(name.clone()),
             }
         }
     }
@@ -75,7 +106,9 @@ impl Render for Button {
             self.color
         };
 
-        let btn = RoundButton::new("btn", self.render_label(), Some(color));
+        let btn = RoundButton::new("btn", self.render_label(cx), Some(color))
+            .size(px(self.size))
+            .focused(self.is_focused);
 
         div().child(btn.on_click(cx.listener(|this, evt, _, cx| {
             Self::handle_click(this, evt, cx);
@@ -91,7 +124,7 @@ pub enum Event {
     Percent,
     Operation(Operation),
     Comma,
-    Noop,
+    NextMode,
 }
 
 impl EventEmitter<Event> for Button {}
@@ -0,0 +1,141 @@
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub enum CaretMotion {
+    Left,
+    Right,
+    Start,
+    End,
+}
+
+// A caret between the characters of the entry line. The anchor is the other
+// end of the selection and equals the position when nothing is selected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Caret {
+    position: usize,
+    anchor: usize,
+}
+
+impl Caret {
+    pub fn at(position: usize) -> Self {
+        Caret {
+            position,
+            anchor: position,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn moved(&self, motion: CaretMotion, select: bool, len: usize) -> Self {
+        let selection = self.selection().filter(|_| !select);
+        let position = match (motion, selection) {
+            (CaretMotion::Left, Some(range)) => range.start,
+            (CaretMotion::Right, Some(range)) => range.end,
+            (CaretMotion::Left, None) => self.position.saturating_sub(1),
+            (CaretMotion::Right, None) => self.position + 1,
+            (CaretMotion::Start, _) => 0,
+            (CaretMotion::End, _) => len,
+        }
+        .min(len);
+
+        Caret {
+            position,
+            anchor: if select {
+                self.anchor.min(len)
+            } else {
+                position
+            },
+        }
+    }
+
+    pub fn selection(&self) -> Option<Range<usize>> {
+        (self.anchor != self.position)
+            .then(|| self.position.min(self.anchor)..self.position.max(self.anchor))
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selection().is_some_and(|range| range.contains(&index))
+    }
+
+    pub fn is_at_end(&self, len: usize) -> bool {
+        self.selection().is_none() && self.position >= len
+    }
+
+    // The characters replaced by typing at the caret.
+    pub fn range(&self) -> Range<usize> {
+        self.selection().unwrap_or(self.position..self.position)
+    }
+
+    // The characters removed by backspace, or by delete when `forward` is set.
+    pub fn deletion(&self, forward: bool, len: usize) -> Option<Range<usize>> {
+        if let Some(range) = self.selection() {
+            return Some(range);
+        }
+
+        match forward {
+            true => (self.position < len).then(|| self.position..self.position + 1),
+            false => (self.position > 0).then(|| self.position - 1..self.position),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_caret {
+    use super::*;
+
+    #[test]
+    fn moves_within_bounds() {
+        let caret = Caret::at(0).moved(CaretMotion::Left, false, 3);
+        assert_eq!(caret, Caret::at(0));
+
+        let caret = caret.moved(CaretMotion::Right, false, 3);
+        assert_eq!(caret.position(), 1);
+        assert_eq!(caret.moved(CaretMotion::End, false, 3), Caret::at(3));
+        assert_eq!(
+            Caret::at(3).moved(CaretMotion::Right, false, 3),
+            Caret::at(3)
+        );
+        assert_eq!(
+            Caret::at(2).moved(CaretMotion::Start, false, 3),
+            Caret::at(0)
+        );
+    }
+
+    #[test]
+    fn extends_and_collapses_selection() {
+        let caret =
+            Caret::at(1)
+                .moved(CaretMotion::Right, true, 5)
+                .moved(CaretMotion::Right, true, 5);
+
+        assert_eq!(caret.selection(), Some(1..3));
+        assert_eq!(caret.range(), 1..3);
+        assert!(caret.is_selected(2));
+        assert!(!caret.is_selected(3));
+        assert_eq!(caret.moved(CaretMotion::Left, false, 5), Caret::at(1));
+        assert_eq!(caret.moved(CaretMotion::Right, false, 5), Caret::at(3));
+
+        let caret = Caret::at(2).moved(CaretMotion::Start, true, 5);
+        assert_eq!(caret.selection(), Some(0..2));
+    }
+
+    #[test]
+    fn deletes_around_caret() {
+        assert_eq!(Caret::at(2).deletion(false, 4), Some(1..2));
+        assert_eq!(Caret::at(2).deletion(true, 4), Some(2..3));
+        assert_eq!(Caret::at(0).deletion(false, 4), None);
+        assert_eq!(Caret::at(4).deletion(true, 4), None);
+
+        let caret = Caret::at(3).moved(CaretMotion::Left, true, 4);
+        assert_eq!(caret.deletion(false, 4), Some(2..3));
+    }
+
+    #[test]
+    fn detects_end() {
+        assert!(Caret::at(4).is_at_end(4));
+        assert!(!Caret::at(3).is_at_end(4));
+        assert!(!Caret::at(4).moved(CaretMotion::Left, true, 4).is_at_end(4));
+    }
+}
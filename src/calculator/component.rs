@@ -1,17 +1,54 @@
-use std::fmt::Display;
+use std::{fmt::Display, ops::Range, str::FromStr};
 
 use super::{
-    button::{Button as CalculatorButton, ButtonLabel, Event as ButtonEvent},
-    calculation::{Calculation, Operation},
+    accessibility::{focus_target, Announcer, ButtonLabel},
+    action::{CalculatorAction, FocusDirection},
+    button::{render_label, Button as CalculatorButton, Event as ButtonEvent},
+    calculation::{Calculation, OperandValue, Operation},
+    caret::{Caret, CaretMotion},
+    duration::TimeUnit,
+    expression::Expression,
+    history::History,
+    layout::{Layout, GAP},
+    mode::Mode,
+    numeric_value::NumericValue,
 };
+use crate::{
+    converter::component::{Event as ConverterEvent, UnitConverter},
+    currency::{
+        component::{CurrencyConverter, Event as CurrencyEvent},
+        provider::FileRateProvider,
+    },
+    dates::component::{DateView, Event as DatesEvent},
+    definitions::{
+        self,
+        component::{DefinitionsView, Event as DefinitionsEvent},
+    },
+    finance::component::{Event as FinanceEvent, FinanceView},
+    graph::component::{Event as GraphEvent, GraphView},
+    paths,
+    round_button::RoundButton,
+    settings::{LayoutSettings, Pane, Settings},
+    solver::component::{Event as SolverEvent, SolverView},
+    statistics::component::{Event as StatisticsEvent, StatisticsView},
+    tape::component::{Event as TapeEvent, TapeView},
+};
+use dashu::Decimal;
 use gpui::{
-    div, impl_actions, prelude::*, px, rgb, rgba, App, Context, Entity, KeyBinding, SharedString,
-    Window,
+    div, prelude::*, px, rgb, rgba, App, Context, Entity, FocusHandle, Focusable, KeyBinding,
+    KeyDownEvent, Keystroke, SharedString, Window,
 };
 
+const COLUMNS: usize = 4;
+
 #[derive(Debug)]
 pub struct Calculator {
     calculation: Calculation,
+    // The caret and the entry text it was placed in; none while typing at the end.
+    caret: Option<(Caret, String)>,
+    history: History,
+    announcer: Announcer,
+    announcement: SharedString,
     ac_btn: Entity<CalculatorButton>,
     plus_minus_btn: Entity<CalculatorButton>,
     percent_btn: Entity<CalculatorButton>,
@@ -32,10 +69,26 @@ pub struct Calculator {
     nine_btn: Entity<CalculatorButton>,
     calc_btn: Entity<CalculatorButton>,
     comma_btn: Entity<CalculatorButton>,
+    focus_handle: FocusHandle,
+    focused_btn: Option<usize>,
+    mode: Mode,
+    mini: bool,
+    layout: LayoutSettings,
+    exact: bool,
+    converter: Entity<UnitConverter>,
+    currency: Entity<CurrencyConverter>,
+    tape: Entity<TapeView>,
+    statistics: Entity<StatisticsView>,
+    finance: Entity<FinanceView>,
+    dates: Entity<DateView>,
+    solver: Entity<SolverView>,
+    graph: Entity<GraphView>,
+    definitions: Entity<DefinitionsView>,
+    recording: Option<Vec<CalculatorAction>>,
 }
 
 impl Calculator {
-    pub fn new(_window: &mut Window, cx: &mut Context<Self>) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         let light_gray = rgb(0x707070);
         let dark_gray = rgb(0x515251);
         let orange = rgb(0xff9600);
@@ -186,9 +239,9 @@ impl Calculator {
 
         let calc_btn = cx.new(|_| {
             CalculatorButton::new(
-                ButtonLabel::Svg("rocket.svg".to_string()),
+                ButtonLabel::Icon("mode".into()),
                 dark_gray,
-                ButtonEvent::Noop,
+                ButtonEvent::NextMode,
             )
         });
         Self::subscribe_btn(&calc_btn, cx);
@@ -220,8 +273,76 @@ impl Calculator {
         });
         Self::subscribe_btn(&equals_btn, cx);
 
+        let converter = cx.new(|_| UnitConverter::new());
+        cx.subscribe(&converter, |this, _, event, cx| {
+            Self::on_converter_event(this, event, cx);
+        })
+        .detach();
+
+        let currency = cx
+            .new(|_| CurrencyConverter::new(Box::new(FileRateProvider::new(paths::rates_file()))));
+        cx.subscribe(&currency, |this, _, event, cx| {
+            Self::on_currency_event(this, event, cx);
+        })
+        .detach();
+
+        let tape = cx.new(|_| TapeView::new());
+        cx.subscribe(&tape, |this, _, event, cx| {
+            Self::on_tape_event(this, event, cx);
+        })
+        .detach();
+
+        let statistics = cx.new(|_| StatisticsView::new());
+        cx.subscribe(&statistics, |this, _, event, cx| {
+            Self::on_statistics_event(this, event, cx);
+        })
+        .detach();
+
+        let finance = cx.new(|_| FinanceView::new());
+        cx.subscribe(&finance, |this, _, event, cx| {
+            Self::on_finance_event(this, event, cx);
+        })
+        .detach();
+
+        let dates = cx.new(|_| DateView::new());
+        cx.subscribe(&dates, |this, _, event, cx| {
+            Self::on_dates_event(this, event, cx);
+        })
+        .detach();
+
+        let solver = cx.new(|_| SolverView::new());
+        cx.subscribe(&solver, |this, _, event, cx| {
+            Self::on_solver_event(this, event, cx);
+        })
+        .detach();
+
+        let graph = cx.new(|_| GraphView::new());
+        cx.subscribe(&graph, |this, _, event, cx| {
+            Self::on_graph_event(this, event, cx);
+        })
+        .detach();
+
+        let definitions = cx.new(DefinitionsView::new);
+        cx.subscribe(&definitions, |this, _, event, cx| {
+            Self::on_definitions_event(this, event, cx);
+        })
+        .detach();
+
+        // Every change to the result notifies, so the announcement is made here
+        // rather than by each action that can change it.
+        let this = cx.entity();
+        cx.observe(&this, |this, _, cx| this.announce_result(cx))
+            .detach();
+
+        let focus_handle = cx.focus_handle();
+        window.focus(&focus_handle);
+
         Calculator {
             calculation: Calculation::default(),
+            caret: None,
+            history: History::default(),
+            announcer: Announcer::new(&Calculation::default()),
+            announcement: SharedString::default(),
             // row 1
             ac_btn,
             plus_minus_btn,
@@ -247,6 +368,24 @@ impl Calculator {
             zero_btn,
             comma_btn,
             equals_btn,
+            focus_handle,
+            focused_btn: None,
+            mode: Mode::default(),
+            mini: false,
+            layout: Settings::load(&paths::settings_file())
+                .unwrap_or_default()
+                .layout,
+            exact: false,
+            converter,
+            currency,
+            tape,
+            statistics,
+            finance,
+            dates,
+            solver,
+            graph,
+            definitions,
+            recording: None,
         }
     }
 
@@ -260,41 +399,585 @@ impl Calculator {
 
 impl Calculator {
     fn append_number(&mut self, num: usize, cx: &mut Context<Self>) {
+        if self.edit_at_caret(&num.to_string(), cx) {
+            return;
+        }
+
+        let previous = self.calculation.clone();
+
         if self.calculation.is_empty() {
-            self.calculation = Calculation::default();
+            self.calculation.start_over();
         }
 
         self.calculation.append_number(num);
+        self.history.record(previous, &self.calculation);
 
         cx.notify();
     }
 
     fn add_comma(&mut self, cx: &mut Context<Self>) {
+        if self.edit_at_caret(".", cx) {
+            return;
+        }
+
+        let previous = self.calculation.clone();
+
         if self.calculation.is_empty() {
-            self.calculation = Calculation::default();
+            self.calculation.start_over();
         }
 
         self.calculation.add_comma();
+        self.history.record(previous, &self.calculation);
 
         cx.notify();
     }
 
     fn remove_or_clear(&mut self, cx: &mut Context<Self>) {
+        if self.delete_at_caret(false, cx) {
+            return;
+        }
+
+        let previous = self.calculation.clone();
+
         if self.calculation.is_empty() {
             self.calculation = Calculation::default();
+            self.announce("cleared", cx);
         } else {
             self.calculation.remove_last();
         }
+
+        self.history.record(previous, &self.calculation);
+        cx.notify();
+    }
+
+    // The caret only applies to the entry text it was placed in, so any other
+    // change to the calculation sends typing back to the end.
+    fn caret(&self) -> Option<Caret> {
+        self.caret
+            .as_ref()
+            .filter(|(_, text)| *text == self.calculation.current_operation_string())
+            .map(|(caret, _)| *caret)
+    }
+
+    fn entry_len(&self) -> usize {
+        self.calculation.current_operation_string().chars().count()
+    }
+
+    fn set_caret(&mut self, caret: Caret, cx: &mut Context<Self>) {
+        let text = self.calculation.current_operation_string();
+        self.caret = (!caret.is_at_end(text.chars().count())).then_some((caret, text));
+
+        cx.notify();
+    }
+
+    fn move_caret(&mut self, motion: CaretMotion, select: bool, cx: &mut Context<Self>) {
+        if !self.calculation.is_editable() {
+            return;
+        }
+
+        let len = self.entry_len();
+        let caret = self.caret().unwrap_or(Caret::at(len));
+        self.set_caret(caret.moved(motion, select, len), cx);
+    }
+
+    fn place_caret(&mut self, position: usize, cx: &mut Context<Self>) {
+        if self.calculation.is_editable() {
+            self.set_caret(Caret::at(position), cx);
+        }
+    }
+
+    // Returns false while the caret is at the end, where keys append as usual.
+    fn edit_at_caret(&mut self, text: &str, cx: &mut Context<Self>) -> bool {
+        let Some(caret) = self.caret() else {
+            return false;
+        };
+
+        self.replace_entry(caret.range(), text, cx);
+        true
+    }
+
+    fn delete_at_caret(&mut self, forward: bool, cx: &mut Context<Self>) -> bool {
+        let Some(caret) = self.caret() else {
+            return false;
+        };
+
+        if let Some(range) = caret.deletion(forward, self.entry_len()) {
+            self.replace_entry(range, "", cx);
+        }
+        true
+    }
+
+    fn replace_entry(&mut self, range: Range<usize>, text: &str, cx: &mut Context<Self>) {
+        let Some(edited) = self.calculation.edit(range.clone(), text) else {
+            return;
+        };
+
+        let previous = std::mem::replace(&mut self.calculation, edited);
+        self.history.record(previous, &self.calculation);
+
+        self.set_caret(Caret::at(range.start + text.chars().count()), cx);
+    }
+
+    pub fn calculation(&self) -> &Calculation {
+        &self.calculation
+    }
+
+    pub fn insert_value(&mut self, value: NumericValue, cx: &mut Context<Self>) {
+        self.insert_operand(OperandValue::Number(value), cx);
+    }
+
+    // Evaluates with `x` bound to the current entry and enters the result.
+    pub fn evaluate(&mut self, expression: &Expression, cx: &mut Context<Self>) -> Option<String> {
+        let x = self.calculation.current_value();
+        let value = NumericValue::new(expression.evaluate(x.val())?);
+        let text = value.to_string();
+
+        self.insert_value(value, cx);
+
+        Some(text)
+    }
+
+    fn insert_operand(&mut self, value: OperandValue, cx: &mut Context<Self>) {
+        let previous = self.calculation.clone();
+
+        self.calculation.replace_current_operand(value);
+        self.history.record(previous, &self.calculation);
+
+        cx.notify();
+    }
+
+    fn paste(&mut self, cx: &mut Context<Self>) {
+        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+
+        if self.mode == Mode::Statistics {
+            self.statistics
+                .update(cx, |statistics, cx| statistics.paste(&text, cx));
+            return;
+        }
+
+        if self.mode == Mode::Graph {
+            self.graph.update(cx, |graph, cx| graph.paste(&text, cx));
+            return;
+        }
+
+        if let Ok(value) = Decimal::from_str(text.trim()) {
+            self.insert_value(NumericValue::new(value), cx);
+            return;
+        }
+
+        let defined = self
+            .definitions
+            .update(cx, |definitions, cx| definitions.define(&text, cx));
+
+        // Anything else is entered as a calculation, like `2 + 3 * 4`.
+        if !defined {
+            if let Some(calculation) = Calculation::parse(&text) {
+                let previous = std::mem::replace(&mut self.calculation, calculation);
+                self.history.record(previous, &self.calculation);
+                cx.notify();
+            }
+        }
+    }
+
+    fn apply_function(&mut self, index: usize, cx: &mut Context<Self>) {
+        let value = self.calculation.current_value();
+
+        if let Some(result) = self.definitions.read(cx).apply(index, value.val(), cx) {
+            self.insert_value(NumericValue::new(result), cx);
+        }
+    }
+
+    fn toggle_recording(&mut self, cx: &mut Context<Self>) {
+        match self.recording.take() {
+            Some(actions) => {
+                let binding = self
+                    .definitions
+                    .update(cx, |definitions, cx| definitions.record(actions, cx));
+
+                if let Some((key, index)) = binding {
+                    bind_macro(cx, &key, index);
+                }
+            }
+            None => self.recording = Some(vec![]),
+        }
+
+        let recording = self.recording.is_some();
+        self.definitions.update(cx, |definitions, cx| {
+            definitions.set_recording(recording, cx)
+        });
+        cx.notify();
+    }
+
+    fn run_macro(&mut self, index: usize, cx: &mut Context<Self>) {
+        for action in self.definitions.read(cx).actions(index, cx) {
+            self.perform(&action, cx);
+        }
+    }
+
+    fn add_data_point(&mut self, cx: &mut Context<Self>) {
+        let value = self.calculation.current_value();
+        self.statistics
+            .update(cx, |statistics, cx| statistics.add(&value, cx));
+
+        let previous = self.calculation.clone();
+        self.calculation = Calculation::default();
+        self.history.record(previous, &self.calculation);
+
+        cx.notify();
+    }
+
+    fn undo(&mut self, cx: &mut Context<Self>) {
+        if let Some(calculation) = self.history.undo(&self.calculation) {
+            self.calculation = calculation;
+            self.announce("undo", cx);
+        }
+    }
+
+    fn redo(&mut self, cx: &mut Context<Self>) {
+        if let Some(calculation) = self.history.redo(&self.calculation) {
+            self.calculation = calculation;
+            self.announce("redo", cx);
+        }
+    }
+
+    fn append_operation(&mut self, op: Operation, cx: &mut Context<Self>) {
+        if self.edit_at_caret(&op.to_string(), cx) {
+            return;
+        }
+
+        let previous = self.calculation.clone();
+        let replace = self.calculation.has_pending_operation();
+        self.calculation.append_operation(op.clone());
+        self.history.record(previous, &self.calculation);
+
+        let value = self.calculation.current_value();
+        self.tape
+            .update(cx, |tape, cx| tape.record_entry(value, op, replace, cx));
+
+        cx.notify();
+    }
+
+    fn calculate(&mut self, cx: &mut Context<Self>) {
+        let snapshot = self.calculation.clone();
+        let previous = self.calculation.current_operation_string();
+        if self.exact {
+            self.calculation.make_exact();
+        }
+        let before = self.calculation.clone();
+        self.calculation.calculate();
+        self.history.record(snapshot, &self.calculation);
+
+        let result = self.calculation.current_operation_string();
+        if result != previous {
+            let total = self.calculation.current_value();
+            self.tape
+                .update(cx, |tape, cx| tape.record_calculate(&before, total, cx));
+        }
+
+        cx.notify();
+    }
+
+    fn add_fraction_bar(&mut self, cx: &mut Context<Self>) {
+        let previous = self.calculation.clone();
+
+        self.calculation.add_fraction_bar();
+        self.history.record(previous, &self.calculation);
+
+        cx.notify();
+    }
+
+    fn toggle_fraction(&mut self, cx: &mut Context<Self>) {
+        let previous = self.calculation.clone();
+
+        self.calculation.toggle_fraction();
+        self.history.record(previous, &self.calculation);
+
+        cx.notify();
+    }
+
+    fn append_imaginary(&mut self, cx: &mut Context<Self>) {
+        let previous = self.calculation.clone();
+
+        if self.calculation.is_empty() {
+            self.calculation.start_over();
+        }
+
+        self.calculation.append_imaginary();
+        self.history.record(previous, &self.calculation);
+
+        cx.notify();
+    }
+
+    fn append_time_unit(&mut self, unit: TimeUnit, cx: &mut Context<Self>) {
+        let previous = self.calculation.clone();
+
+        self.calculation.append_time_unit(unit);
+        self.history.record(previous, &self.calculation);
+
+        cx.notify();
+    }
+
+    fn square_root(&mut self, cx: &mut Context<Self>) {
+        let previous = self.calculation.clone();
+
+        self.calculation.square_root();
+        self.history.record(previous, &self.calculation);
+
+        cx.notify();
+    }
+
+    fn toggle_polar(&mut self, cx: &mut Context<Self>) {
+        let previous = self.calculation.clone();
+
+        self.calculation.toggle_polar();
+        self.history.record(previous, &self.calculation);
+
+        cx.notify();
+    }
+
+    fn toggle_exact(&mut self, cx: &mut Context<Self>) {
+        self.exact = !self.exact;
+
+        let announcement = if self.exact {
+            "exact fractions"
+        } else {
+            "decimals"
+        };
+        self.announce(announcement, cx);
+    }
+
+    fn next_mode(&mut self, cx: &mut Context<Self>) {
+        self.mode = self.mode.next();
+        self.announce(format!("{} mode", self.mode), cx);
+    }
+
+    pub fn set_mini(&mut self, mini: bool, cx: &mut Context<Self>) {
+        // Button indices differ between the two keypads.
+        self.set_focused_btn(None, cx);
+        self.mini = mini;
+        cx.notify();
+    }
+
+    fn buttons(&self) -> Vec<Entity<CalculatorButton>> {
+        if self.mini {
+            return vec![
+                self.seven_btn.clone(),
+                self.eight_btn.clone(),
+                self.nine_btn.clone(),
+                self.division_btn.clone(),
+                self.four_btn.clone(),
+                self.five_btn.clone(),
+                self.six_btn.clone(),
+                self.multiplication_btn.clone(),
+                self.one_btn.clone(),
+                self.two_btn.clone(),
+                self.three_btn.clone(),
+                self.subtraction_btn.clone(),
+                self.zero_btn.clone(),
+                self.comma_btn.clone(),
+                self.equals_btn.clone(),
+                self.addition_btn.clone(),
+            ];
+        }
+
+        vec![
+            // Row 1
+            self.ac_btn.clone(),
+            self.plus_minus_btn.clone(),
+            self.percent_btn.clone(),
+            self.division_btn.clone(),
+            // Row 2
+            self.seven_btn.clone(),
+            self.eight_btn.clone(),
+            self.nine_btn.clone(),
+            self.multiplication_btn.clone(),
+            // Row 3
+            self.four_btn.clone(),
+            self.five_btn.clone(),
+            self.six_btn.clone(),
+            self.subtraction_btn.clone(),
+            // Row 4
+            self.one_btn.clone(),
+            self.two_btn.clone(),
+            self.three_btn.clone(),
+            self.addition_btn.clone(),
+            // Row 5,
+            self.calc_btn.clone(),
+            self.zero_btn.clone(),
+            self.comma_btn.clone(),
+            self.equals_btn.clone(),
+        ]
+    }
+
+    fn move_focus(&mut self, direction: &FocusDirection, cx: &mut Context<Self>) {
+        let next = focus_target(self.focused_btn, direction, self.buttons().len(), COLUMNS);
+
+        self.set_focused_btn(Some(next), cx);
+    }
+
+    fn announce_result(&mut self, cx: &mut Context<Self>) {
+        if let Some(announcement) = self.announcer.announce(&self.calculation) {
+            self.announce(announcement, cx);
+        }
+    }
+
+    // gpui has no platform accessibility API to speak through yet, so the last
+    // announcement is kept for screen reader bridges to read over JSON-RPC.
+    fn announce(&mut self, announcement: impl Into<SharedString>, cx: &mut Context<Self>) {
+        self.announcement = announcement.into();
+        cx.notify();
+    }
+
+    pub fn announcement(&self) -> &SharedString {
+        &self.announcement
+    }
+
+    fn set_focused_btn(&mut self, index: Option<usize>, cx: &mut Context<Self>) {
+        let buttons = self.buttons();
+
+        if let Some(btn) = self.focused_btn.and_then(|i| buttons.get(i)) {
+            btn.update(cx, |btn, cx| btn.set_focused(false, cx));
+        }
+
+        if let Some(btn) = index.and_then(|i| buttons.get(i)) {
+            btn.update(cx, |btn, cx| btn.set_focused(true, cx));
+
+            let name = btn.read(cx).accessible_name();
+            self.announce(name, cx);
+        }
+
+        self.focused_btn = index;
         cx.notify();
     }
 
-    fn render_result(&self) -> impl IntoElement {
+    fn activate_focused(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(btn) = self
+            .focused_btn
+            .and_then(|i| self.buttons().get(i).cloned())
+        else {
+            return false;
+        };
+
+        btn.update(cx, |btn, cx| btn.activate(cx));
+        true
+    }
+
+    fn render_result(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let caret = self.caret();
+        let caret_position = caret
+            .filter(|caret| caret.selection().is_none())
+            .map(|caret| caret.position());
+        let characters: Vec<_> = self
+            .calculation
+            .current_operation_string()
+            .chars()
+            .enumerate()
+            .map(|(index, character)| {
+                let selected = caret.is_some_and(|caret| caret.is_selected(index));
+
+                div()
+                    .id(("entry", index))
+                    .flex()
+                    .flex_row()
+                    .when(caret_position == Some(index), |this| {
+                        this.child(div().w(px(1.)).bg(rgb(0xff9600)))
+                    })
+                    .child(
+                        div()
+                            .when(selected, |this| this.bg(rgba(0xff960060)))
+                            .child(character.to_string()),
+                    )
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        cx.stop_propagation();
+                        this.place_caret(index, cx);
+                    }))
+            })
+            .collect();
+
         div()
+            .id("result")
             .w_full()
             .flex()
             .flex_row()
             .justify_end()
-            .child(self.calculation.current_operation_string())
+            .children(characters)
+            .on_click(cx.listener(|this, _, _, cx| {
+                let len = this.entry_len();
+                this.place_caret(len, cx);
+            }))
+            .on_drag(
+                DraggedValue(self.calculation.current_value()),
+                |value, _, _, cx| cx.new(|_| value.clone()),
+            )
+    }
+
+    fn render_preview(&self) -> Option<impl IntoElement> {
+        let mut calculation = self.calculation.clone();
+        if self.exact {
+            calculation.make_exact();
+        }
+
+        let preview = calculation.preview()?;
+
+        Some(
+            div()
+                .w_full()
+                .flex()
+                .flex_row()
+                .justify_end()
+                .px(px(5.))
+                .text_sm()
+                .text_color(rgb(0x8a8a8a))
+                .child(format!("= {}", preview)),
+        )
+    }
+
+    fn render_scientific(&self, layout: &Layout, cx: &mut Context<Self>) -> impl IntoElement {
+        let keys = [
+            (
+                ButtonLabel::Icon("sqrt".into()),
+                CalculatorAction::SquareRoot,
+            ),
+            (ButtonLabel::String("i".into()), CalculatorAction::Imaginary),
+            (
+                ButtonLabel::String("∠".into()),
+                CalculatorAction::TogglePolar,
+            ),
+            (
+                ButtonLabel::String("a/b".into()),
+                CalculatorAction::FractionBar,
+            ),
+            (
+                ButtonLabel::String("0.5".into()),
+                CalculatorAction::ToggleFraction,
+            ),
+            (
+                ButtonLabel::String("ab/c".into()),
+                CalculatorAction::ToggleExact,
+            ),
+            (ButtonLabel::String("↶".into()), CalculatorAction::Undo),
+            (ButtonLabel::String("↷".into()), CalculatorAction::Redo),
+        ];
+
+        div()
+            .w(px(layout.pane_width()))
+            .flex()
+            .flex_row()
+            .flex_wrap()
+            .gap(px(GAP))
+            .text_size(px(layout.font(14.)))
+            .children(
+                keys.into_iter()
+                    .enumerate()
+                    .map(|(index, (label, action))| {
+                        let label = render_label(&label, cx);
+                        RoundButton::new(("scientific", index), label, Some(rgb(0x707070)))
+                            .size(px(layout.button))
+                            .on_click(cx.listener(move |this, _, _, cx| this.perform(&action, cx)))
+                    }),
+            )
     }
 
     fn render_past_operations(&self) -> impl IntoElement {
@@ -303,6 +986,10 @@ impl Calculator {
             .flex()
             .flex_row()
             .justify_end()
+            .gap(px(6.))
+            .when(self.exact, |this| {
+                this.child(div().text_xs().text_color(rgb(0xff9600)).child("a b/c"))
+            })
             .child(self.calculation.past_operations_string())
     }
 
@@ -311,7 +998,7 @@ impl Calculator {
             return ButtonLabel::String("AC".into());
         }
 
-        ButtonLabel::Svg("undo.svg".to_string())
+        ButtonLabel::Icon("undo".into())
     }
 }
 
@@ -323,12 +1010,10 @@ impl Calculator {
             }
             ButtonEvent::Operation(op) => match op {
                 Operation::Equals => {
-                    self.calculation.calculate();
-                    cx.notify();
+                    self.calculate(cx);
                 }
                 _ => {
-                    self.calculation.append_operation(op.clone());
-                    cx.notify();
+                    self.append_operation(op.clone(), cx);
                 }
             },
             ButtonEvent::Clear => {
@@ -337,50 +1022,172 @@ impl Calculator {
             ButtonEvent::Comma => {
                 self.add_comma(cx);
             }
+            ButtonEvent::NextMode => {
+                self.next_mode(cx);
+            }
             _ => {}
         }
     }
 
+    fn on_converter_event(&mut self, evt: &ConverterEvent, cx: &mut Context<Self>) {
+        match evt {
+            ConverterEvent::Insert(value) => {
+                self.insert_value(value.clone(), cx);
+            }
+        }
+    }
+
+    fn on_currency_event(&mut self, evt: &CurrencyEvent, cx: &mut Context<Self>) {
+        match evt {
+            CurrencyEvent::Insert(value) => {
+                self.insert_value(value.clone(), cx);
+            }
+        }
+    }
+
+    fn on_statistics_event(&mut self, evt: &StatisticsEvent, cx: &mut Context<Self>) {
+        match evt {
+            StatisticsEvent::Add => {
+                self.add_data_point(cx);
+            }
+            StatisticsEvent::Insert(value) => {
+                self.insert_value(value.clone(), cx);
+            }
+        }
+    }
+
+    fn on_finance_event(&mut self, evt: &FinanceEvent, cx: &mut Context<Self>) {
+        match evt {
+            FinanceEvent::Insert(value) => {
+                self.insert_value(value.clone(), cx);
+            }
+        }
+    }
+
+    fn on_dates_event(&mut self, evt: &DatesEvent, cx: &mut Context<Self>) {
+        match evt {
+            DatesEvent::Insert(value) => {
+                self.insert_value(value.clone(), cx);
+            }
+            DatesEvent::Unit(unit) => {
+                self.append_time_unit(*unit, cx);
+            }
+        }
+    }
+
+    fn on_solver_event(&mut self, evt: &SolverEvent, cx: &mut Context<Self>) {
+        match evt {
+            SolverEvent::Insert(value) => {
+                self.insert_operand(value.clone(), cx);
+            }
+        }
+    }
+
+    fn on_graph_event(&mut self, evt: &GraphEvent, cx: &mut Context<Self>) {
+        match evt {
+            GraphEvent::Insert(value) => {
+                self.insert_value(value.clone(), cx);
+            }
+        }
+    }
+
+    fn on_definitions_event(&mut self, evt: &DefinitionsEvent, cx: &mut Context<Self>) {
+        match evt {
+            &DefinitionsEvent::Apply(index) => {
+                self.apply_function(index, cx);
+            }
+            &DefinitionsEvent::Run(index) => {
+                self.run_macro(index, cx);
+            }
+            DefinitionsEvent::Record => {
+                self.toggle_recording(cx);
+            }
+        }
+    }
+
+    fn on_tape_event(&mut self, evt: &TapeEvent, cx: &mut Context<Self>) {
+        match evt {
+            TapeEvent::Subtotal => {
+                let mut subtotal = self.calculation.clone();
+                if !subtotal.is_empty() {
+                    subtotal.calculate();
+                }
+
+                let value = subtotal.current_value();
+                self.tape
+                    .update(cx, |tape, cx| tape.record_subtotal(value, cx));
+            }
+        }
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
+        if self.tape.read(cx).is_editing() {
+            self.tape
+                .update(cx, |tape, cx| tape.edit_key(&event.keystroke, cx));
+            cx.stop_propagation();
+        }
+    }
+
     fn keyboard(&mut self, a: &CalculatorAction, _: &mut Window, cx: &mut Context<Self>) {
+        self.perform(a, cx);
+    }
+
+    pub fn perform(&mut self, a: &CalculatorAction, cx: &mut Context<Self>) {
+        if let Some(recording) = &mut self.recording {
+            if !matches!(
+                a,
+                CalculatorAction::ToggleRecording | CalculatorAction::RunMacro(_)
+            ) {
+                recording.push(a.clone());
+            }
+        }
+
         match a {
             CalculatorAction::Backspace => {
                 self.remove_or_clear(cx);
 
                 self.ac_btn.update(cx, |btn, cx| btn.set_clicked(cx));
             }
+            CalculatorAction::Delete => {
+                self.delete_at_caret(true, cx);
+            }
             CalculatorAction::Calculate => {
-                self.calculation.calculate();
-                cx.notify();
+                if self.activate_focused(cx) {
+                    return;
+                }
+
+                self.calculate(cx);
 
                 self.equals_btn.update(cx, |btn, cx| btn.set_clicked(cx));
             }
+            CalculatorAction::Focus(direction) => {
+                self.move_focus(direction, cx);
+            }
+            CalculatorAction::Activate => {
+                self.activate_focused(cx);
+            }
+            CalculatorAction::Unfocus => {
+                self.set_focused_btn(None, cx);
+            }
             CalculatorAction::Op(Operation::Addition) => {
-                self.calculation
-                    .append_operation(OperationButton::Plus.into());
-                cx.notify();
+                self.append_operation(OperationButton::Plus.into(), cx);
 
                 self.addition_btn.update(cx, |btn, cx| btn.set_clicked(cx));
             }
             CalculatorAction::Op(Operation::Subtraction) => {
-                self.calculation
-                    .append_operation(OperationButton::Minus.into());
-                cx.notify();
+                self.append_operation(OperationButton::Minus.into(), cx);
 
                 self.subtraction_btn
                     .update(cx, |btn, cx| btn.set_clicked(cx));
             }
             CalculatorAction::Op(Operation::Multiplication) => {
-                self.calculation
-                    .append_operation(OperationButton::Times.into());
-                cx.notify();
+                self.append_operation(OperationButton::Times.into(), cx);
 
                 self.multiplication_btn
                     .update(cx, |btn, cx| btn.set_clicked(cx));
             }
             CalculatorAction::Op(Operation::Division) => {
-                self.calculation
-                    .append_operation(OperationButton::Division.into());
-                cx.notify();
+                self.append_operation(OperationButton::Division.into(), cx);
             }
             &CalculatorAction::Numeric(val) => {
                 self.append_number(val, cx);
@@ -402,81 +1209,219 @@ impl Calculator {
                 self.add_comma(cx);
                 self.comma_btn.update(cx, |btn, cx| btn.set_clicked(cx));
             }
+            CalculatorAction::NextMode => {
+                self.next_mode(cx);
+                self.calc_btn.update(cx, |btn, cx| btn.set_clicked(cx));
+            }
+            CalculatorAction::FractionBar => {
+                self.add_fraction_bar(cx);
+            }
+            CalculatorAction::ToggleFraction => {
+                self.toggle_fraction(cx);
+            }
+            CalculatorAction::ToggleExact => {
+                self.toggle_exact(cx);
+            }
+            CalculatorAction::Imaginary => {
+                self.append_imaginary(cx);
+            }
+            CalculatorAction::SquareRoot => {
+                self.square_root(cx);
+            }
+            CalculatorAction::TogglePolar => {
+                self.toggle_polar(cx);
+            }
+            CalculatorAction::AddDataPoint => {
+                self.add_data_point(cx);
+            }
+            CalculatorAction::Undo => {
+                self.undo(cx);
+            }
+            CalculatorAction::Redo => {
+                self.redo(cx);
+            }
+            CalculatorAction::Paste => {
+                self.paste(cx);
+            }
+            &CalculatorAction::TimeUnit(unit) => {
+                self.append_time_unit(unit, cx);
+            }
+            CalculatorAction::ToggleRecording => {
+                self.toggle_recording(cx);
+            }
+            &CalculatorAction::RunMacro(index) => {
+                self.run_macro(index, cx);
+            }
+            // Left and right keep moving across the buttons while one has focus.
+            &CalculatorAction::MoveCaret(motion) => match (motion, self.focused_btn) {
+                (CaretMotion::Left, Some(_)) => self.move_focus(&FocusDirection::Left, cx),
+                (CaretMotion::Right, Some(_)) => self.move_focus(&FocusDirection::Right, cx),
+                _ => self.move_caret(motion, false, cx),
+            },
+            &CalculatorAction::SelectCaret(motion) => {
+                self.move_caret(motion, true, cx);
+            }
             _ => {}
         }
     }
 }
 
 impl Render for Calculator {
-    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.ac_btn
             .update(cx, |btn, _| btn.label(self.render_ac_label()));
+        self.converter.update(cx, |converter, _| {
+            converter.set_input(self.calculation.current_value())
+        });
+        self.currency.update(cx, |currency, _| {
+            currency.set_input(self.calculation.current_value())
+        });
+        self.finance.update(cx, |finance, _| {
+            finance.set_input(self.calculation.current_value())
+        });
+        self.dates.update(cx, |dates, _| {
+            dates.set_input(self.calculation.current_operand_value())
+        });
+        self.solver.update(cx, |solver, _| {
+            solver.set_input(self.calculation.current_value())
+        });
 
-        let btns: Vec<Entity<CalculatorButton>> = vec![
-            // Row 1
-            self.ac_btn.clone(),
-            self.plus_minus_btn.clone(),
-            self.percent_btn.clone(),
-            self.division_btn.clone(),
-            // Row 2
-            self.seven_btn.clone(),
-            self.eight_btn.clone(),
-            self.nine_btn.clone(),
-            self.multiplication_btn.clone(),
-            // Row 3
-            self.four_btn.clone(),
-            self.five_btn.clone(),
-            self.six_btn.clone(),
-            self.subtraction_btn.clone(),
-            // Row 4
-            self.one_btn.clone(),
-            self.two_btn.clone(),
-            self.three_btn.clone(),
-            self.addition_btn.clone(),
-            // Row 5,
-            self.calc_btn.clone(),
-            self.zero_btn.clone(),
-            self.comma_btn.clone(),
-            self.equals_btn.clone(),
-        ];
+        let viewport = window.viewport_size();
+        let layout = Layout::new(
+            &self.layout,
+            f32::from(viewport.width),
+            f32::from(viewport.height),
+            self.mini,
+        );
+        let btns = self.buttons();
+        for btn in &btns {
+            btn.update(cx, |btn, _| btn.set_size(layout.button));
+        }
+
+        // Mini mode shows only the entry and a compact keypad, and the tape
+        // moves out of the mode area while the tape pane is open.
+        let mode = (!self.mini).then_some(self.mode);
+        let tape_pane = layout.has(Pane::Tape);
+        let key_context = if self.tape.read(cx).is_editing() {
+            ANNOTATION_CONTEXT
+        } else {
+            CONTEXT
+        };
 
         div()
             .id("calculator")
-            .focusable()
-            .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
+            .key_context(key_context)
             .on_action(cx.listener(Self::keyboard))
+            .on_key_down(cx.listener(Self::on_key_down))
+            .on_drop(cx.listener(|this, value: &DraggedValue, _, cx| {
+                this.insert_value(value.0.clone(), cx)
+            }))
             .items_center()
             .shadow_lg()
             .bg(rgba(0x45454580))
             .text_color(rgb(0xffffff))
             .p_1p5()
             .h_full()
-            .children([
-                div().w_full().mt_9(),
-                div()
-                    .w_full()
-                    .text_lg()
-                    .text_color(rgb(0xcccccc))
-                    .child(self.render_past_operations()),
+            .child(div().w_full().mt_9())
+            .when(!self.mini, |this| {
+                this.child(
+                    div()
+                        .w_full()
+                        .text_size(px(layout.font(18.)))
+                        .text_color(rgb(0xcccccc))
+                        .child(self.render_past_operations()),
+                )
+            })
+            .child(
                 div()
                     .w_full()
-                    .text_2xl()
+                    .text_size(px(layout.font(24.)))
                     .px(px(5.))
-                    .child(self.render_result()),
+                    .child(self.render_result(cx)),
+            )
+            .children(self.render_preview())
+            .when(mode == Some(Mode::Conversion), |this| {
+                this.child(self.converter.clone())
+            })
+            .when(mode == Some(Mode::Currency), |this| {
+                this.child(self.currency.clone())
+            })
+            .when(mode == Some(Mode::Tape) && !tape_pane, |this| {
+                this.child(self.tape.clone())
+            })
+            .when(mode == Some(Mode::Statistics), |this| {
+                this.child(self.statistics.clone())
+            })
+            .when(mode == Some(Mode::Finance), |this| {
+                this.child(self.finance.clone())
+            })
+            .when(mode == Some(Mode::Dates), |this| {
+                this.child(self.dates.clone())
+            })
+            .when(mode == Some(Mode::Solver), |this| {
+                this.child(self.solver.clone())
+            })
+            .when(mode == Some(Mode::Graph), |this| {
+                this.child(self.graph.clone())
+            })
+            .when(!self.mini, |this| this.child(self.definitions.clone()))
+            .child(
                 div()
                     .w_full()
                     .flex()
-                    .flex_wrap()
                     .flex_row()
                     .justify_center()
-                    .items_center()
-                    .gap(px(5.))
-                    .children(btns),
-            ])
+                    .gap(px(GAP))
+                    .child(
+                        div()
+                            .w(px(layout.keypad_width()))
+                            .flex()
+                            .flex_wrap()
+                            .flex_row()
+                            .items_center()
+                            .gap(px(GAP))
+                            .text_size(px(layout.font(16.)))
+                            .children(btns),
+                    )
+                    .when(layout.has(Pane::Scientific), |this| {
+                        this.child(self.render_scientific(&layout, cx))
+                    })
+                    .when(tape_pane, |this| {
+                        this.child(
+                            div()
+                                .w(px(layout.pane_width()))
+                                .overflow_hidden()
+                                .child(self.tape.clone()),
+                        )
+                    }),
+            )
+    }
+}
+
+impl Focusable for Calculator {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DraggedValue(pub NumericValue);
+
+impl Render for DraggedValue {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px(px(6.))
+            .py(px(2.))
+            .rounded_md()
+            .bg(rgba(0x454545e0))
+            .text_color(rgb(0xffffff))
+            .child(self.0.to_string())
     }
 }
 
 const CONTEXT: &str = "Calculator";
+const ANNOTATION_CONTEXT: &str = "TapeAnnotation";
 
 pub fn init(cx: &mut App) {
     cx.bind_keys([
@@ -514,7 +1459,121 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("9", CalculatorAction::Numeric(9), Some(CONTEXT)),
         KeyBinding::new(",", CalculatorAction::Comma, Some(CONTEXT)),
         KeyBinding::new(".", CalculatorAction::Comma, Some(CONTEXT)),
+        KeyBinding::new(
+            "tab",
+            CalculatorAction::Focus(FocusDirection::Next),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "shift-tab",
+            CalculatorAction::Focus(FocusDirection::Previous),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "up",
+            CalculatorAction::Focus(FocusDirection::Up),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "down",
+            CalculatorAction::Focus(FocusDirection::Down),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "left",
+            CalculatorAction::MoveCaret(CaretMotion::Left),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "right",
+            CalculatorAction::MoveCaret(CaretMotion::Right),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "home",
+            CalculatorAction::MoveCaret(CaretMotion::Start),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "end",
+            CalculatorAction::MoveCaret(CaretMotion::End),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "shift-left",
+            CalculatorAction::SelectCaret(CaretMotion::Left),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "shift-right",
+            CalculatorAction::SelectCaret(CaretMotion::Right),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "shift-home",
+            CalculatorAction::SelectCaret(CaretMotion::Start),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "shift-end",
+            CalculatorAction::SelectCaret(CaretMotion::End),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new("delete", CalculatorAction::Delete, Some(CONTEXT)),
+        KeyBinding::new("space", CalculatorAction::Activate, Some(CONTEXT)),
+        KeyBinding::new("escape", CalculatorAction::Unfocus, Some(CONTEXT)),
+        KeyBinding::new("m", CalculatorAction::NextMode, Some(CONTEXT)),
+        KeyBinding::new("f", CalculatorAction::FractionBar, Some(CONTEXT)),
+        KeyBinding::new("d", CalculatorAction::ToggleFraction, Some(CONTEXT)),
+        KeyBinding::new("r", CalculatorAction::ToggleExact, Some(CONTEXT)),
+        KeyBinding::new("i", CalculatorAction::Imaginary, Some(CONTEXT)),
+        KeyBinding::new("@", CalculatorAction::SquareRoot, Some(CONTEXT)),
+        KeyBinding::new("p", CalculatorAction::TogglePolar, Some(CONTEXT)),
+        KeyBinding::new("shift-enter", CalculatorAction::AddDataPoint, Some(CONTEXT)),
+        KeyBinding::new("secondary-z", CalculatorAction::Undo, Some(CONTEXT)),
+        KeyBinding::new("secondary-shift-z", CalculatorAction::Redo, Some(CONTEXT)),
+        KeyBinding::new("secondary-v", CalculatorAction::Paste, Some(CONTEXT)),
+        KeyBinding::new(
+            "shift-d",
+            CalculatorAction::TimeUnit(TimeUnit::Days),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "h",
+            CalculatorAction::TimeUnit(TimeUnit::Hours),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "'",
+            CalculatorAction::TimeUnit(TimeUnit::Minutes),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new(
+            "\"",
+            CalculatorAction::TimeUnit(TimeUnit::Seconds),
+            Some(CONTEXT),
+        ),
+        KeyBinding::new("q", CalculatorAction::ToggleRecording, Some(CONTEXT)),
     ]);
+
+    for (key, index) in definitions::component::key_bindings(cx) {
+        bind_macro(cx, &key, index);
+    }
+}
+
+// Keys come from the user's definitions file, so skip any that do not parse
+// instead of letting KeyBinding::new panic.
+fn bind_macro(cx: &mut App, key: &str, index: usize) {
+    if key
+        .split_whitespace()
+        .all(|keystroke| Keystroke::parse(keystroke).is_ok())
+    {
+        cx.bind_keys([KeyBinding::new(
+            key,
+            CalculatorAction::RunMacro(index),
+            Some(CONTEXT),
+        )]);
+    }
 }
 
 #[derive(Debug)]
@@ -555,15 +1614,3 @@ impl From<OperationButton> for Operation {
         }
     }
 }
-
-#[derive(Clone, Debug, PartialEq, Deserialize, JsonSchema)]
-enum CalculatorAction {
-    Numeric(usize),
-    Op(Operation),
-    Calculate,
-    Backspace,
-    Comma,
-    NoAction,
-}
-
-impl_actions!(calculator, [CalculatorAction]);
@@ -0,0 +1,230 @@
+use super::calculation::Calculation;
+use std::collections::VecDeque;
+
+// Snapshots are measured with their digits, so a few huge exact values evict as
+// much as many small ones.
+const MEMORY_BUDGET: usize = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct History {
+    undo: VecDeque<Calculation>,
+    redo: Vec<Calculation>,
+    budget: usize,
+    used: usize,
+}
+
+impl History {
+    pub fn new(budget: usize) -> Self {
+        History {
+            undo: VecDeque::new(),
+            redo: vec![],
+            budget,
+            used: 0,
+        }
+    }
+
+    pub fn record(&mut self, previous: Calculation, current: &Calculation) {
+        if previous == *current {
+            return;
+        }
+
+        for snapshot in self.redo.drain(..) {
+            self.used -= snapshot.footprint();
+        }
+
+        self.used += previous.footprint();
+        self.undo.push_back(previous);
+
+        self.trim();
+    }
+
+    pub fn undo(&mut self, current: &Calculation) -> Option<Calculation> {
+        let snapshot = self.undo.pop_back()?;
+        self.used -= snapshot.footprint();
+
+        self.used += current.footprint();
+        self.redo.push(current.clone());
+
+        self.trim();
+
+        Some(snapshot)
+    }
+
+    pub fn redo(&mut self, current: &Calculation) -> Option<Calculation> {
+        let snapshot = self.redo.pop()?;
+        self.used -= snapshot.footprint();
+
+        self.used += current.footprint();
+        self.undo.push_back(current.clone());
+
+        self.trim();
+
+        Some(snapshot)
+    }
+
+    fn trim(&mut self) {
+        while self.used > self.budget {
+            let Some(oldest) = self.undo.pop_front() else {
+                break;
+            };
+
+            self.used -= oldest.footprint();
+        }
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(MEMORY_BUDGET)
+    }
+}
+
+#[cfg(test)]
+mod test_history {
+    use super::*;
+    use crate::calculator::{calculation::Operation, numeric_value::NumericValue};
+    use dashu_float::DBig;
+    use std::str::FromStr;
+
+    fn keyed(numbers: &[usize]) -> Calculation {
+        let mut calculation = Calculation::default();
+        for num in numbers {
+            calculation.append_number(*num);
+        }
+
+        calculation
+    }
+
+    fn apply(
+        history: &mut History,
+        calculation: &mut Calculation,
+        mutation: impl FnOnce(&mut Calculation),
+    ) {
+        let previous = calculation.clone();
+        mutation(calculation);
+        history.record(previous, calculation);
+    }
+
+    #[test]
+    fn undoes_and_redoes_append() {
+        let mut history = History::default();
+        let mut calculation = keyed(&[1]);
+
+        apply(&mut history, &mut calculation, |c| c.append_number(2));
+        assert_eq!(calculation, keyed(&[1, 2]));
+
+        let undone = history.undo(&calculation).unwrap();
+        assert_eq!(undone, keyed(&[1]));
+
+        let redone = history.redo(&undone).unwrap();
+        assert_eq!(redone, keyed(&[1, 2]));
+    }
+
+    #[test]
+    fn restores_cleared_calculation() {
+        let mut history = History::default();
+        let mut calculation = keyed(&[4, 2]);
+        calculation.append_operation(Operation::Addition);
+
+        let before_clear = calculation.clone();
+        apply(&mut history, &mut calculation, |c| {
+            *c = Calculation::default()
+        });
+
+        assert_eq!(history.undo(&calculation), Some(before_clear));
+    }
+
+    #[test]
+    fn restores_overwritten_operation() {
+        let mut history = History::default();
+        let mut calculation = keyed(&[5]);
+
+        apply(&mut history, &mut calculation, |c| {
+            c.append_operation(Operation::Addition)
+        });
+        let with_addition = calculation.clone();
+
+        apply(&mut history, &mut calculation, |c| {
+            c.append_operation(Operation::Division)
+        });
+
+        assert_eq!(history.undo(&calculation), Some(with_addition));
+    }
+
+    #[test]
+    fn undoes_calculate_and_paste() {
+        let mut history = History::default();
+        let mut calculation = keyed(&[5]);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_number(3);
+
+        let before_calculate = calculation.clone();
+        apply(&mut history, &mut calculation, |c| c.calculate());
+
+        let calculated = calculation.clone();
+        apply(&mut history, &mut calculation, |c| {
+            c.replace_current_value(NumericValue::new(dbig!(42)))
+        });
+
+        let undone = history.undo(&calculation).unwrap();
+        assert_eq!(undone, calculated);
+        assert_eq!(history.undo(&undone), Some(before_calculate));
+    }
+
+    #[test]
+    fn ignores_unchanged_calculation() {
+        let mut history = History::default();
+        let mut calculation = keyed(&[5]);
+
+        apply(&mut history, &mut calculation, |c| c.calculate());
+
+        assert_eq!(history.undo(&calculation), None);
+    }
+
+    #[test]
+    fn new_change_discards_redo() {
+        let mut history = History::default();
+        let mut calculation = keyed(&[1]);
+
+        apply(&mut history, &mut calculation, |c| c.append_number(2));
+        calculation = history.undo(&calculation).unwrap();
+        apply(&mut history, &mut calculation, |c| c.append_number(3));
+
+        assert_eq!(history.redo(&calculation), None);
+    }
+
+    #[test]
+    fn drops_oldest_snapshots_over_budget() {
+        let mut history = History::new(keyed(&[1]).footprint() * 2);
+        let mut calculation = keyed(&[1]);
+
+        for num in 2..6usize {
+            apply(&mut history, &mut calculation, |c| {
+                c.replace_current_value(NumericValue::new(DBig::from(num)))
+            });
+        }
+
+        // Only the last two changes fit in the budget.
+        calculation = history.undo(&calculation).unwrap();
+        calculation = history.undo(&calculation).unwrap();
+        assert_eq!(calculation.current_value(), NumericValue::new(dbig!(3)));
+        assert_eq!(history.undo(&calculation), None);
+    }
+
+    #[test]
+    fn counts_digits_against_the_budget() {
+        let mut history = History::new(keyed(&[1]).footprint() * 4);
+        let mut calculation = keyed(&[1]);
+        let huge = DBig::from_str(&"7".repeat(4000)).unwrap();
+
+        apply(&mut history, &mut calculation, |c| {
+            c.replace_current_value(NumericValue::new(huge))
+        });
+        apply(&mut history, &mut calculation, |c| {
+            c.replace_current_value(NumericValue::new(dbig!(2)))
+        });
+
+        // The snapshot holding the huge value alone is over the budget.
+        assert_eq!(history.undo(&calculation), None);
+    }
+}
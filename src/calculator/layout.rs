@@ -0,0 +1,131 @@
+use crate::settings::{LayoutSettings, Pane};
+
+pub const GAP: f32 = 5.;
+
+const COLUMNS: f32 = 4.;
+const PADDING: f32 = 12.;
+const BASE_BUTTON: f32 = 42.;
+// Height taken by the title bar, previous operations, entry and preview.
+const CHROME: f32 = 110.;
+const MINI_CHROME: f32 = 82.;
+// Each pane is half as wide as the keypad.
+const PANE_RATIO: f32 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    pub button: f32,
+    pub panes: Vec<Pane>,
+    scale: f32,
+}
+
+impl Layout {
+    pub fn new(settings: &LayoutSettings, width: f32, height: f32, mini: bool) -> Layout {
+        let panes = match mini {
+            true => vec![],
+            false => settings
+                .breakpoints
+                .iter()
+                .filter(|breakpoint| width >= breakpoint.min_width)
+                .max_by(|a, b| a.min_width.total_cmp(&b.min_width))
+                .map(|breakpoint| breakpoint.panes.clone())
+                .unwrap_or_default(),
+        };
+        let (rows, chrome) = match mini {
+            true => (4., MINI_CHROME),
+            false => (5., CHROME),
+        };
+
+        let keypad = (width - PADDING) / (1. + PANE_RATIO * panes.len() as f32);
+        let by_width = (keypad - GAP * (COLUMNS - 1.)) / COLUMNS;
+        let by_height = (height - chrome) / rows - GAP;
+        let button = by_width
+            .min(by_height)
+            .floor()
+            .min(settings.max_button)
+            .max(settings.min_button);
+
+        Layout {
+            button,
+            panes,
+            scale: button / BASE_BUTTON,
+        }
+    }
+
+    pub fn has(&self, pane: Pane) -> bool {
+        self.panes.contains(&pane)
+    }
+
+    pub fn keypad_width(&self) -> f32 {
+        self.button * COLUMNS + GAP * (COLUMNS - 1.)
+    }
+
+    pub fn pane_width(&self) -> f32 {
+        self.keypad_width() * PANE_RATIO
+    }
+
+    // Scales a font size designed for the default 42 px buttons.
+    pub fn font(&self, size: f32) -> f32 {
+        (size * self.scale).round()
+    }
+}
+
+#[cfg(test)]
+mod test_layout {
+    use super::*;
+
+    #[test]
+    fn keeps_default_window_at_base_size() {
+        let layout = Layout::new(&LayoutSettings::default(), 198., 350., false);
+
+        assert_eq!(layout.button, BASE_BUTTON);
+        assert_eq!(layout.font(24.), 24.);
+        assert!(layout.panes.is_empty());
+        assert_eq!(
+            Layout::new(&LayoutSettings::default(), 198., 270., true).button,
+            BASE_BUTTON
+        );
+    }
+
+    #[test]
+    fn scales_with_the_smaller_dimension() {
+        let settings = LayoutSettings::default();
+
+        let tall = Layout::new(&settings, 260., 800., false);
+        assert_eq!(tall.button, 58.);
+        assert_eq!(tall.font(24.), 33.);
+
+        let short = Layout::new(&settings, 260., 350., false);
+        assert_eq!(short.button, 43.);
+
+        assert_eq!(Layout::new(&settings, 100., 200., false).button, 32.);
+        assert_eq!(Layout::new(&settings, 300., 2000., false).button, 68.);
+    }
+
+    #[test]
+    fn adds_panes_at_breakpoints() {
+        let settings = LayoutSettings::default();
+
+        let wide = Layout::new(&settings, 400., 500., false);
+        assert_eq!(wide.panes, vec![Pane::Scientific]);
+        assert!(wide.has(Pane::Scientific));
+        assert_eq!(wide.button, 60.);
+        assert_eq!(wide.pane_width(), 127.5);
+
+        let wider = Layout::new(&settings, 640., 500., false);
+        assert!(wider.has(Pane::Tape));
+        assert!(Layout::new(&settings, 640., 500., true).panes.is_empty());
+    }
+
+    #[test]
+    fn respects_configured_limits() {
+        let settings = LayoutSettings {
+            min_button: 40.,
+            max_button: 48.,
+            breakpoints: vec![],
+        };
+
+        assert_eq!(Layout::new(&settings, 1000., 1000., false).button, 48.);
+        assert_eq!(Layout::new(&settings, 150., 300., false).button, 40.);
+        assert!(Layout::new(&settings, 1000., 1000., false).panes.is_empty());
+    }
+}
@@ -1,4 +1,10 @@
+pub mod accessibility;
+pub mod action;
 pub mod button;
-pub mod calculation;
+pub mod caret;
 pub mod component;
-mod numeric_value;
+pub mod history;
+pub mod layout;
+pub mod mode;
+
+pub use calc_core::{calculation, complex, duration, expression, numeric_value};
@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Mode {
+    #[default]
+    Standard,
+    Conversion,
+    Currency,
+    Tape,
+    Statistics,
+    Finance,
+    Dates,
+    Solver,
+    Graph,
+}
+
+impl Mode {
+    const ALL: [Mode; 9] = [
+        Mode::Standard,
+        Mode::Conversion,
+        Mode::Currency,
+        Mode::Tape,
+        Mode::Statistics,
+        Mode::Finance,
+        Mode::Dates,
+        Mode::Solver,
+        Mode::Graph,
+    ];
+
+    pub fn next(&self) -> Mode {
+        let index = Self::ALL.iter().position(|mode| mode == self).unwrap_or(0);
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mode::Standard => f.write_str("Standard"),
+            Mode::Conversion => f.write_str("Units"),
+            Mode::Currency => f.write_str("Currency"),
+            Mode::Tape => f.write_str("Tape"),
+            Mode::Statistics => f.write_str("Stats"),
+            Mode::Finance => f.write_str("Finance"),
+            Mode::Dates => f.write_str("Dates"),
+            Mode::Solver => f.write_str("Solver"),
+            Mode::Graph => f.write_str("Graph"),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use super::units::{convert, Unit, Units};
+use crate::calculator::numeric_value::NumericValue;
+use gpui::{div, prelude::*, px, rgb, Context, EventEmitter, SharedString, Window};
+
+pub struct UnitConverter {
+    units: Units,
+    category: usize,
+    from: usize,
+    to: usize,
+    input: NumericValue,
+}
+
+impl UnitConverter {
+    pub fn new() -> Self {
+        UnitConverter {
+            units: Units::bundled(),
+            category: 0,
+            from: 0,
+            to: 1,
+            input: NumericValue::default(),
+        }
+    }
+
+    pub fn set_input(&mut self, input: NumericValue) {
+        self.input = input;
+    }
+
+    pub fn converted(&self) -> NumericValue {
+        NumericValue::new(convert(self.input.val(), self.from_unit(), self.to_unit()))
+    }
+
+    fn units(&self) -> &[Unit] {
+        &self.units.categories[self.category].units
+    }
+
+    fn from_unit(&self) -> &Unit {
+        &self.units()[self.from]
+    }
+
+    fn to_unit(&self) -> &Unit {
+        &self.units()[self.to]
+    }
+
+    fn next_category(&mut self, cx: &mut Context<Self>) {
+        self.category = (self.category + 1) % self.units.categories.len();
+        self.from = 0;
+        self.to = 1.min(self.units().len() - 1);
+        cx.notify();
+    }
+
+    fn next_from(&mut self, cx: &mut Context<Self>) {
+        self.from = (self.from + 1) % self.units().len();
+        cx.notify();
+    }
+
+    fn next_to(&mut self, cx: &mut Context<Self>) {
+        self.to = (self.to + 1) % self.units().len();
+        cx.notify();
+    }
+
+    fn swap(&mut self, cx: &mut Context<Self>) {
+        std::mem::swap(&mut self.from, &mut self.to);
+        cx.notify();
+    }
+
+    fn insert(&mut self, cx: &mut Context<Self>) {
+        cx.emit(Event::Insert(self.converted()));
+    }
+}
+
+impl Default for UnitConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for UnitConverter {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let category: SharedString = self.units.categories[self.category].name.clone().into();
+        let from: SharedString = self.from_unit().symbol.clone().into();
+        let to: SharedString = self.to_unit().symbol.clone().into();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.))
+            .px(px(5.))
+            .text_xs()
+            .text_color(rgb(0xcccccc))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .justify_between()
+                    .child(
+                        div()
+                            .id("category")
+                            .child(category)
+                            .on_click(cx.listener(|this, _, _, cx| this.next_category(cx))),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .flex_row()
+                            .gap(px(4.))
+                            .child(
+                                div()
+                                    .id("from")
+                                    .child(from)
+                                    .on_click(cx.listener(|this, _, _, cx| this.next_from(cx))),
+                            )
+                            .child(
+                                div()
+                                    .id("swap")
+                                    .child("⇄")
+                                    .on_click(cx.listener(|this, _, _, cx| this.swap(cx))),
+                            )
+                            .child(
+                                div()
+                                    .id("to")
+                                    .child(to)
+                                    .on_click(cx.listener(|this, _, _, cx| this.next_to(cx))),
+                            ),
+                    ),
+            )
+            .child(
+                div()
+                    .id("converted")
+                    .w_full()
+                    .flex()
+                    .flex_row()
+                    .justify_end()
+                    .overflow_hidden()
+                    .text_sm()
+                    .text_color(rgb(0xffffff))
+                    .child(self.converted().to_string())
+                    .on_click(cx.listener(|this, _, _, cx| this.insert(cx))),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Insert(NumericValue),
+}
+
+impl EventEmitter<Event> for UnitConverter {}
@@ -0,0 +1,236 @@
+use crate::calculator::numeric_value::PRECISION;
+use anyhow::{anyhow, Result};
+use dashu_float::DBig;
+use std::{
+    ops::{Add, Div, Mul, Sub},
+    str::FromStr,
+};
+
+const BUNDLED_UNITS: &str = include_str!("../../data/units.json");
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    pub symbol: String,
+    numerator: DBig,
+    denominator: DBig,
+    offset: DBig,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Category {
+    pub name: String,
+    pub units: Vec<Unit>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Units {
+    pub categories: Vec<Category>,
+}
+
+#[derive(Deserialize)]
+struct UnitsDefinition {
+    categories: Vec<CategoryDefinition>,
+}
+
+#[derive(Deserialize)]
+struct CategoryDefinition {
+    name: String,
+    units: Vec<UnitDefinition>,
+}
+
+#[derive(Deserialize)]
+struct UnitDefinition {
+    name: String,
+    symbol: String,
+    factor: String,
+    offset: Option<String>,
+}
+
+impl Units {
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED_UNITS).expect("Bundled units should be valid")
+    }
+
+    pub fn parse(json: &str) -> Result<Self> {
+        let definition: UnitsDefinition = serde_json::from_str(json)?;
+
+        let categories = definition
+            .categories
+            .into_iter()
+            .map(|category| {
+                let units = category
+                    .units
+                    .into_iter()
+                    .map(Unit::try_from)
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(Category {
+                    name: category.name,
+                    units,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Units { categories })
+    }
+}
+
+impl Unit {
+    fn base_value(&self, value: &DBig) -> DBig {
+        value
+            .clone()
+            .with_precision(PRECISION)
+            .value()
+            .add(&self.offset)
+            .mul(&self.numerator)
+            .div(&self.denominator)
+            .with_precision(PRECISION)
+            .value()
+    }
+
+    fn unit_value(&self, value: &DBig) -> DBig {
+        value
+            .clone()
+            .with_precision(PRECISION)
+            .value()
+            .mul(&self.denominator)
+            .div(&self.numerator)
+            .sub(&self.offset)
+            .with_precision(PRECISION)
+            .value()
+    }
+}
+
+impl TryFrom<UnitDefinition> for Unit {
+    type Error = anyhow::Error;
+
+    fn try_from(definition: UnitDefinition) -> Result<Self> {
+        let (numerator, denominator) = match definition.factor.split_once('/') {
+            Some((numerator, denominator)) => (parse(numerator)?, parse(denominator)?),
+            None => (parse(&definition.factor)?, DBig::ONE),
+        };
+
+        if numerator == DBig::ZERO || denominator == DBig::ZERO {
+            return Err(anyhow!("Unit {} has a zero factor", definition.name));
+        }
+
+        let offset = match definition.offset {
+            Some(offset) => parse(&offset)?,
+            None => DBig::ZERO,
+        };
+
+        Ok(Unit {
+            symbol: definition.symbol,
+            numerator,
+            denominator,
+            offset,
+        })
+    }
+}
+
+fn parse(value: &str) -> Result<DBig> {
+    DBig::from_str(value.trim()).map_err(|err| anyhow!("Invalid factor {}: {}", value, err))
+}
+
+pub fn convert(value: &DBig, from: &Unit, to: &Unit) -> DBig {
+    to.unit_value(&from.base_value(value))
+}
+
+#[cfg(test)]
+mod test_units {
+    use super::*;
+
+    #[test]
+    fn loads_bundled_categories() {
+        let units = Units::bundled();
+
+        let names: Vec<&str> = units.categories.iter().map(|c| c.name.as_str()).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "Length",
+                "Mass",
+                "Temperature",
+                "Volume",
+                "Area",
+                "Speed",
+                "Data size",
+                "Time",
+                "Pressure",
+                "Energy"
+            ]
+        );
+        assert!(units.categories.iter().all(|c| c.units.len() > 1));
+    }
+
+    #[test]
+    fn rejects_zero_factor() {
+        let json = r#"{ "categories": [{ "name": "Broken", "units": [
+            { "name": "Nothing", "symbol": "n", "factor": "0" }
+        ] }] }"#;
+
+        assert!(Units::parse(json).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_factor() {
+        let json = r#"{ "categories": [{ "name": "Broken", "units": [
+            { "name": "Nothing", "symbol": "n", "factor": "abc" }
+        ] }] }"#;
+
+        assert!(Units::parse(json).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_convert {
+    use super::*;
+
+    fn convert_in(category: &str, value: DBig, from: &str, to: &str) -> DBig {
+        let units = Units::bundled();
+        let category = units
+            .categories
+            .iter()
+            .find(|c| c.name == category)
+            .unwrap();
+        let unit = |symbol: &str| category.units.iter().find(|u| u.symbol == symbol).unwrap();
+
+        convert(&value, unit(from), unit(to))
+    }
+
+    #[test]
+    fn converts_length() {
+        assert_eq!(convert_in("Length", dbig!(1), "ft", "m"), dbig!(0.3048));
+        assert_eq!(convert_in("Length", dbig!(1), "mi", "ft"), dbig!(5280));
+    }
+
+    #[test]
+    fn converts_to_same_unit() {
+        assert_eq!(convert_in("Mass", dbig!(12.5), "kg", "kg"), dbig!(12.5));
+    }
+
+    #[test]
+    fn converts_temperature_with_offset() {
+        assert_eq!(
+            convert_in("Temperature", dbig!(100), "°C", "°F"),
+            dbig!(212)
+        );
+        assert_eq!(convert_in("Temperature", dbig!(32), "°F", "°C"), dbig!(0));
+        assert_eq!(
+            convert_in("Temperature", dbig!(0), "°C", "K"),
+            dbig!(273.15)
+        );
+    }
+
+    #[test]
+    fn converts_fractional_factors() {
+        assert_eq!(convert_in("Speed", dbig!(36), "km/h", "m/s"), dbig!(10));
+    }
+
+    #[test]
+    fn converts_binary_data_sizes() {
+        assert_eq!(convert_in("Data size", dbig!(1), "GiB", "MiB"), dbig!(1024));
+        assert_eq!(convert_in("Data size", dbig!(8), "bit", "B"), dbig!(1));
+    }
+}
@@ -0,0 +1,175 @@
+use super::{provider::RateProvider, rates::Rates};
+use crate::calculator::numeric_value::{round_to_places, NumericValue};
+use gpui::{div, prelude::*, px, rgb, Context, EventEmitter, SharedString, Window};
+
+const DISPLAY_PLACES: usize = 2;
+
+pub struct CurrencyConverter {
+    provider: Box<dyn RateProvider>,
+    rates: Result<Rates, SharedString>,
+    from: usize,
+    to: usize,
+    input: NumericValue,
+}
+
+impl CurrencyConverter {
+    pub fn new(provider: Box<dyn RateProvider>) -> Self {
+        let rates = Self::load(provider.as_ref());
+
+        CurrencyConverter {
+            provider,
+            rates,
+            from: 0,
+            to: 1,
+            input: NumericValue::default(),
+        }
+    }
+
+    pub fn set_input(&mut self, input: NumericValue) {
+        self.input = input;
+    }
+
+    fn load(provider: &dyn RateProvider) -> Result<Rates, SharedString> {
+        provider
+            .fetch()
+            .map_err(|err| format!("{}: {}", provider.describe(), err).into())
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.rates = Self::load(self.provider.as_ref());
+        self.from = 0;
+        self.to = 1;
+        cx.notify();
+    }
+
+    fn pair(&self) -> Option<(&str, &str)> {
+        let currencies = self.rates.as_ref().ok()?.currencies();
+
+        Some((
+            currencies.get(self.from % currencies.len())?,
+            currencies.get(self.to % currencies.len())?,
+        ))
+    }
+
+    fn converted(&self) -> Option<NumericValue> {
+        let rates = self.rates.as_ref().ok()?;
+        let (from, to) = self.pair()?;
+
+        rates
+            .convert(self.input.val(), from, to)
+            .map(NumericValue::new)
+            .ok()
+    }
+
+    fn next_from(&mut self, cx: &mut Context<Self>) {
+        self.from += 1;
+        cx.notify();
+    }
+
+    fn next_to(&mut self, cx: &mut Context<Self>) {
+        self.to += 1;
+        cx.notify();
+    }
+
+    fn swap(&mut self, cx: &mut Context<Self>) {
+        std::mem::swap(&mut self.from, &mut self.to);
+        cx.notify();
+    }
+
+    fn insert(&mut self, cx: &mut Context<Self>) {
+        if let Some(value) = self.converted() {
+            cx.emit(Event::Insert(value));
+        }
+    }
+
+    fn render_status(&self) -> impl IntoElement {
+        match &self.rates {
+            Ok(rates) => div().child(format!("{} rates as of {}", rates.base, rates.as_of)),
+            Err(err) => div()
+                .overflow_hidden()
+                .text_color(rgb(0xff6961))
+                .child(err.clone()),
+        }
+    }
+}
+
+impl Render for CurrencyConverter {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (from, to): (SharedString, SharedString) = self
+            .pair()
+            .map(|(from, to)| (from.to_string().into(), to.to_string().into()))
+            .unwrap_or_else(|| ("—".into(), "—".into()));
+        let converted = self
+            .converted()
+            .map(|value| round_to_places(value.val(), DISPLAY_PLACES).to_string())
+            .unwrap_or_default();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.))
+            .px(px(5.))
+            .text_xs()
+            .text_color(rgb(0xcccccc))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .justify_between()
+                    .gap(px(4.))
+                    .child(self.render_status())
+                    .child(
+                        div()
+                            .id("reload")
+                            .child("↻")
+                            .on_click(cx.listener(|this, _, _, cx| this.reload(cx))),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .justify_end()
+                    .gap(px(4.))
+                    .child(
+                        div()
+                            .id("from")
+                            .child(from)
+                            .on_click(cx.listener(|this, _, _, cx| this.next_from(cx))),
+                    )
+                    .child(
+                        div()
+                            .id("swap")
+                            .child("⇄")
+                            .on_click(cx.listener(|this, _, _, cx| this.swap(cx))),
+                    )
+                    .child(
+                        div()
+                            .id("to")
+                            .child(to)
+                            .on_click(cx.listener(|this, _, _, cx| this.next_to(cx))),
+                    ),
+            )
+            .child(
+                div()
+                    .id("converted")
+                    .w_full()
+                    .flex()
+                    .flex_row()
+                    .justify_end()
+                    .overflow_hidden()
+                    .text_sm()
+                    .text_color(rgb(0xffffff))
+                    .child(converted)
+                    .on_click(cx.listener(|this, _, _, cx| this.insert(cx))),
+            )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Insert(NumericValue),
+}
+
+impl EventEmitter<Event> for CurrencyConverter {}
@@ -0,0 +1,3 @@
+pub mod component;
+pub mod provider;
+pub mod rates;
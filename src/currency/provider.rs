@@ -0,0 +1,102 @@
+use super::rates::Rates;
+use anyhow::{anyhow, Result};
+use std::{fs, path::PathBuf};
+
+pub trait RateProvider {
+    fn fetch(&self) -> Result<Rates>;
+
+    fn describe(&self) -> String;
+}
+
+pub struct FileRateProvider {
+    path: PathBuf,
+}
+
+impl FileRateProvider {
+    pub fn new(path: PathBuf) -> Self {
+        FileRateProvider { path }
+    }
+}
+
+impl RateProvider for FileRateProvider {
+    fn fetch(&self) -> Result<Rates> {
+        let contents = fs::read_to_string(&self.path)?;
+
+        match self.path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Rates::from_json(&contents),
+            Some("csv") => Rates::from_csv(&contents),
+            _ => Err(anyhow!(
+                "Unsupported rates file {}",
+                self.path.to_string_lossy()
+            )),
+        }
+    }
+
+    fn describe(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+}
+
+#[cfg(test)]
+mod test_provider {
+    use super::*;
+    use dashu::Decimal;
+
+    struct StandInProvider {
+        body: &'static str,
+    }
+
+    impl RateProvider for StandInProvider {
+        fn fetch(&self) -> Result<Rates> {
+            Rates::from_json(self.body)
+        }
+
+        fn describe(&self) -> String {
+            "stand-in".to_string()
+        }
+    }
+
+    fn convert_with(provider: &dyn RateProvider, amount: Decimal) -> Result<Decimal> {
+        provider.fetch()?.convert(&amount, "USD", "EUR")
+    }
+
+    #[test]
+    fn converts_through_any_provider() {
+        let provider = StandInProvider {
+            body: r#"{ "as_of": "2025-02-14", "base": "USD", "rates": { "EUR": "0.5" } }"#,
+        };
+
+        assert_eq!(convert_with(&provider, dbig!(3)).unwrap(), dbig!(1.5));
+    }
+
+    #[test]
+    fn reads_rates_from_file() {
+        let path = std::env::temp_dir().join("hello-gpui-test-rates.csv");
+        fs::write(&path, "as_of,2025-02-14\nbase,USD\nEUR,0.5\n").unwrap();
+
+        let provider = FileRateProvider::new(path.clone());
+        let rates = provider.fetch();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(rates.unwrap().as_of, "2025-02-14");
+    }
+
+    #[test]
+    fn fails_on_missing_file() {
+        let provider = FileRateProvider::new(PathBuf::from("/does/not/exist/rates.json"));
+
+        assert!(provider.fetch().is_err());
+    }
+
+    #[test]
+    fn fails_on_unknown_extension() {
+        let path = std::env::temp_dir().join("hello-gpui-test-rates.txt");
+        fs::write(&path, "as_of,2025-02-14\nbase,USD\n").unwrap();
+
+        let provider = FileRateProvider::new(path.clone());
+        let rates = provider.fetch();
+        fs::remove_file(&path).unwrap();
+
+        assert!(rates.is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::calculator::numeric_value::PRECISION;
+use anyhow::{anyhow, Result};
+use dashu::Decimal;
+use std::{
+    collections::BTreeMap,
+    ops::{Div, Mul},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rates {
+    pub as_of: String,
+    pub base: String,
+    rates: BTreeMap<String, Decimal>,
+}
+
+#[derive(Deserialize)]
+struct RatesDefinition {
+    as_of: String,
+    base: String,
+    rates: BTreeMap<String, RateValue>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RateValue {
+    Text(String),
+    Number(serde_json::Number),
+}
+
+impl Rates {
+    pub fn from_json(json: &str) -> Result<Self> {
+        let definition: RatesDefinition = serde_json::from_str(json)?;
+
+        let rates = definition
+            .rates
+            .into_iter()
+            .map(|(currency, rate)| {
+                let rate = match rate {
+                    RateValue::Text(text) => text,
+                    RateValue::Number(number) => number.to_string(),
+                };
+
+                Ok((currency, parse_rate(&rate)?))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()?;
+
+        Self::new(definition.as_of, definition.base, rates)
+    }
+
+    pub fn from_csv(csv: &str) -> Result<Self> {
+        let mut as_of = None;
+        let mut base = None;
+        let mut rates = BTreeMap::new();
+
+        for (index, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(',')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .ok_or_else(|| anyhow!("Line {} is not a key,value pair", index + 1))?;
+
+            match key {
+                "currency" => continue,
+                "as_of" => as_of = Some(value.to_string()),
+                "base" => base = Some(value.to_string()),
+                currency => {
+                    rates.insert(currency.to_string(), parse_rate(value)?);
+                }
+            }
+        }
+
+        Self::new(
+            as_of.ok_or_else(|| anyhow!("Missing as_of row"))?,
+            base.ok_or_else(|| anyhow!("Missing base row"))?,
+            rates,
+        )
+    }
+
+    fn new(as_of: String, base: String, mut rates: BTreeMap<String, Decimal>) -> Result<Self> {
+        if let Some((currency, _)) = rates.iter().find(|(_, rate)| **rate <= Decimal::ZERO) {
+            return Err(anyhow!("Rate for {} must be positive", currency));
+        }
+
+        rates.insert(base.clone(), Decimal::ONE);
+
+        Ok(Rates { as_of, base, rates })
+    }
+
+    pub fn currencies(&self) -> Vec<&str> {
+        self.rates.keys().map(String::as_str).collect()
+    }
+
+    pub fn convert(&self, amount: &Decimal, from: &str, to: &str) -> Result<Decimal> {
+        let from_rate = self
+            .rates
+            .get(from)
+            .ok_or_else(|| anyhow!("Unknown currency {}", from))?;
+        let to_rate = self
+            .rates
+            .get(to)
+            .ok_or_else(|| anyhow!("Unknown currency {}", to))?;
+
+        Ok(amount
+            .clone()
+            .with_precision(PRECISION)
+            .value()
+            .div(from_rate)
+            .mul(to_rate)
+            .with_precision(PRECISION)
+            .value())
+    }
+}
+
+fn parse_rate(value: &str) -> Result<Decimal> {
+    Decimal::from_str(value).map_err(|err| anyhow!("Invalid rate {}: {}", value, err))
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn parses_json() {
+        let rates = Rates::from_json(
+            r#"{ "as_of": "2025-02-14", "base": "USD", "rates": { "EUR": "0.95", "MXN": 20.5 } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(rates.as_of, "2025-02-14");
+        assert_eq!(rates.currencies(), vec!["EUR", "MXN", "USD"]);
+    }
+
+    #[test]
+    fn parses_csv() {
+        let rates = Rates::from_csv(
+            "# Rates exported by hand\nas_of,2025-02-14\nbase,USD\ncurrency,rate\nEUR,0.95\nMXN, 20.5\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            rates,
+            Rates::from_json(
+                r#"{ "as_of": "2025-02-14", "base": "USD", "rates": { "EUR": "0.95", "MXN": "20.5" } }"#,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_csv_without_base() {
+        assert!(Rates::from_csv("as_of,2025-02-14\nEUR,0.95\n").is_err());
+    }
+
+    #[test]
+    fn rejects_non_positive_rates() {
+        assert!(Rates::from_csv("as_of,2025-02-14\nbase,USD\nEUR,0\n").is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_convert {
+    use super::*;
+
+    fn rates() -> Rates {
+        Rates::from_csv("as_of,2025-02-14\nbase,USD\nEUR,0.8\nMXN,20\n").unwrap()
+    }
+
+    #[test]
+    fn converts_from_base() {
+        assert_eq!(
+            rates().convert(&dbig!(10), "USD", "MXN").unwrap(),
+            dbig!(200)
+        );
+    }
+
+    #[test]
+    fn converts_between_quoted_currencies() {
+        assert_eq!(
+            rates().convert(&dbig!(100), "MXN", "EUR").unwrap(),
+            dbig!(4)
+        );
+    }
+
+    #[test]
+    fn fails_on_unknown_currency() {
+        assert!(rates().convert(&dbig!(1), "USD", "JPY").is_err());
+    }
+}
@@ -0,0 +1,269 @@
+use super::date::{seconds_from_days, Date};
+use crate::{
+    action_button::action_button,
+    calculator::{calculation::OperandValue, duration::TimeUnit, numeric_value::NumericValue},
+};
+use anyhow::{anyhow, Result};
+use dashu::Decimal;
+use gpui::{div, prelude::*, px, rgb, Context, EventEmitter, SharedString, Window};
+use std::ops::Neg;
+
+pub struct DateView {
+    from: Date,
+    to: Date,
+    input: OperandValue,
+    error: Option<SharedString>,
+}
+
+impl DateView {
+    pub fn new() -> Self {
+        DateView {
+            from: Date::today(),
+            to: Date::today(),
+            input: OperandValue::default(),
+            error: None,
+        }
+    }
+
+    pub fn set_input(&mut self, input: OperandValue) {
+        self.input = input;
+    }
+
+    fn input_date(&self) -> Result<Date> {
+        match &self.input {
+            OperandValue::Number(value) => Date::from_value(value.val()),
+            _ => Err(anyhow!("Enter dates as YYYYMMDD")),
+        }
+    }
+
+    fn input_seconds(&self) -> Option<Decimal> {
+        match &self.input {
+            OperandValue::Number(value) => Some(seconds_from_days(value.val())),
+            OperandValue::Duration(duration) => Some(duration.seconds()),
+            _ => None,
+        }
+    }
+
+    fn store_from(&mut self, cx: &mut Context<Self>) {
+        match self.input_date() {
+            Ok(date) => {
+                self.from = date;
+                self.error = None;
+            }
+            Err(err) => self.error = Some(err.to_string().into()),
+        }
+
+        cx.notify();
+    }
+
+    fn store_to(&mut self, cx: &mut Context<Self>) {
+        match self.input_date() {
+            Ok(date) => {
+                self.to = date;
+                self.error = None;
+            }
+            Err(err) => self.error = Some(err.to_string().into()),
+        }
+
+        cx.notify();
+    }
+
+    fn results(&self) -> Vec<(&'static str, Decimal)> {
+        vec![
+            ("days", Decimal::from(self.from.days_until(self.to))),
+            ("weeks", self.from.weeks_until(self.to)),
+            ("months", Decimal::from(self.from.months_until(self.to))),
+            (
+                "business days",
+                Decimal::from(self.from.business_days_until(self.to)),
+            ),
+        ]
+    }
+
+    fn shifted(&self) -> Vec<(&'static str, Result<(SharedString, Decimal)>)> {
+        let Some(seconds) = self.input_seconds() else {
+            return vec![];
+        };
+
+        [
+            ("from + entry", seconds.clone()),
+            ("from − entry", seconds.neg()),
+        ]
+        .into_iter()
+        .map(|(label, seconds)| {
+            let shifted = self
+                .from
+                .shift(&seconds)
+                .map(|shifted| (shifted.to_string().into(), shifted.date().to_value()));
+
+            (label, shifted)
+        })
+        .collect()
+    }
+
+    fn render_date(
+        &self,
+        id: &'static str,
+        label: &'static str,
+        date: Date,
+        cx: &mut Context<Self>,
+        store: impl Fn(&mut Self, &mut Context<Self>) + 'static,
+        today: impl Fn(&mut Self) + 'static,
+    ) -> impl IntoElement {
+        div()
+            .w_full()
+            .flex()
+            .flex_row()
+            .justify_between()
+            .gap(px(4.))
+            .child(
+                div()
+                    .id(id)
+                    .w(px(32.))
+                    .child(label)
+                    .on_click(cx.listener(move |this, _, _, cx| store(this, cx))),
+            )
+            .child(
+                div()
+                    .id((id, 1usize))
+                    .flex_grow()
+                    .flex()
+                    .justify_end()
+                    .text_color(rgb(0xffffff))
+                    .child(date.to_string())
+                    .on_click(cx.listener(move |_, _, _, cx| {
+                        cx.emit(Event::Insert(NumericValue::new(date.to_value())))
+                    })),
+            )
+            .child(action_button(
+                (id, 2usize),
+                "Today",
+                None,
+                cx,
+                move |this, cx| {
+                    today(this);
+                    cx.notify();
+                },
+            ))
+    }
+
+    fn render_unit(
+        &self,
+        index: usize,
+        unit: TimeUnit,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(("unit", index))
+            .px(px(6.))
+            .rounded_sm()
+            .bg(rgb(0x515251))
+            .child(unit.to_string())
+            .on_click(cx.listener(move |_, _, _, cx| cx.emit(Event::Unit(unit))))
+    }
+
+    fn render_result(
+        &self,
+        index: usize,
+        label: &'static str,
+        text: SharedString,
+        value: Option<Decimal>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let value = value.map(NumericValue::new);
+
+        div()
+            .id(("result", index))
+            .w_full()
+            .flex()
+            .flex_row()
+            .justify_between()
+            .child(label)
+            .child(div().text_color(rgb(0xffffff)).child(text))
+            .on_click(cx.listener(move |_, _, _, cx| {
+                if let Some(value) = &value {
+                    cx.emit(Event::Insert(value.clone()));
+                }
+            }))
+    }
+}
+
+impl Default for DateView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for DateView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let units: Vec<_> = TimeUnit::ALL
+            .into_iter()
+            .enumerate()
+            .map(|(index, unit)| self.render_unit(index, unit, cx))
+            .collect();
+
+        let mut rows: Vec<(&'static str, SharedString, Option<Decimal>)> = self
+            .results()
+            .into_iter()
+            .map(|(label, value)| {
+                (
+                    label,
+                    NumericValue::new(value.clone()).to_string().into(),
+                    Some(value),
+                )
+            })
+            .collect();
+        rows.extend(
+            self.shifted()
+                .into_iter()
+                .map(|(label, shifted)| match shifted {
+                    Ok((text, value)) => (label, text, Some(value)),
+                    Err(err) => (label, err.to_string().into(), None),
+                }),
+        );
+
+        let results: Vec<_> = rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, (label, text, value))| self.render_result(index, label, text, value, cx))
+            .collect();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.))
+            .px(px(5.))
+            .text_xs()
+            .text_color(rgb(0xcccccc))
+            .child(self.render_date(
+                "from",
+                "From",
+                self.from,
+                cx,
+                |this, cx| this.store_from(cx),
+                |this| this.from = Date::today(),
+            ))
+            .child(self.render_date(
+                "to",
+                "To",
+                self.to,
+                cx,
+                |this, cx| this.store_to(cx),
+                |this| this.to = Date::today(),
+            ))
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_color(rgb(0xff6060)).child(error))
+            })
+            .child(div().flex().flex_row().gap(px(3.)).children(units))
+            .children(results)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Insert(NumericValue),
+    Unit(TimeUnit),
+}
+
+impl EventEmitter<Event> for DateView {}
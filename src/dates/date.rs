@@ -0,0 +1,295 @@
+use crate::calculator::numeric_value::precise;
+use anyhow::{anyhow, Result};
+use dashu::Decimal;
+use std::{
+    fmt::Display,
+    ops::{Div, Mul},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    days: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateTime {
+    date: Date,
+    seconds: i64,
+}
+
+impl Date {
+    pub fn new(year: i64, month: u32, day: u32) -> Result<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(anyhow!("Invalid date {:04}-{:02}-{:02}", year, month, day));
+        }
+
+        Ok(Date {
+            days: days_from_civil(year, month, day),
+        })
+    }
+
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0);
+
+        Date {
+            days: seconds.div_euclid(SECONDS_PER_DAY),
+        }
+    }
+
+    pub fn from_value(value: &Decimal) -> Result<Self> {
+        if value.fract() != Decimal::ZERO {
+            return Err(anyhow!("Enter dates as YYYYMMDD"));
+        }
+
+        let value: i64 = value
+            .to_int()
+            .value()
+            .try_into()
+            .map_err(|_| anyhow!("Enter dates as YYYYMMDD"))?;
+
+        if !(10101..=99991231).contains(&value) {
+            return Err(anyhow!("Enter dates as YYYYMMDD"));
+        }
+
+        Date::new(
+            value / 10000,
+            (value / 100 % 100) as u32,
+            (value % 100) as u32,
+        )
+    }
+
+    pub fn to_value(self) -> Decimal {
+        let (year, month, day) = civil_from_days(self.days);
+
+        Decimal::from(year * 10000 + month as i64 * 100 + day as i64)
+    }
+
+    pub fn weekday(self) -> u32 {
+        (self.days + 3).rem_euclid(7) as u32
+    }
+
+    pub fn is_business_day(self) -> bool {
+        self.weekday() < 5
+    }
+
+    pub fn days_until(self, other: Date) -> i64 {
+        other.days - self.days
+    }
+
+    pub fn weeks_until(self, other: Date) -> Decimal {
+        precise(&Decimal::from(self.days_until(other))).div(Decimal::from(7u8))
+    }
+
+    pub fn months_until(self, other: Date) -> i64 {
+        if other < self {
+            return -other.months_until(self);
+        }
+
+        let (from_year, from_month, from_day) = civil_from_days(self.days);
+        let (to_year, to_month, to_day) = civil_from_days(other.days);
+
+        let months = (to_year - from_year) * 12 + to_month as i64 - from_month as i64;
+
+        if to_day < from_day && to_day < days_in_month(to_year, to_month) {
+            months - 1
+        } else {
+            months
+        }
+    }
+
+    pub fn business_days_until(self, other: Date) -> i64 {
+        if other < self {
+            return -other.business_days_until(self);
+        }
+
+        let days = self.days_until(other);
+        let full_weeks = days / 7;
+        let remainder = (1..=days % 7)
+            .filter(|offset| {
+                Date {
+                    days: self.days + full_weeks * 7 + offset,
+                }
+                .is_business_day()
+            })
+            .count() as i64;
+
+        full_weeks * 5 + remainder
+    }
+
+    pub fn shift(self, seconds: &Decimal) -> Result<DateTime> {
+        let seconds: i64 = seconds
+            .round()
+            .to_int()
+            .value()
+            .try_into()
+            .map_err(|_| anyhow!("Duration is out of range"))?;
+
+        let days = self
+            .days
+            .checked_add(seconds.div_euclid(SECONDS_PER_DAY))
+            .filter(|days| civil_from_days(*days).0 <= 9999)
+            .filter(|days| civil_from_days(*days).0 >= 1)
+            .ok_or_else(|| anyhow!("Date is out of range"))?;
+
+        Ok(DateTime {
+            date: Date { days },
+            seconds: seconds.rem_euclid(SECONDS_PER_DAY),
+        })
+    }
+}
+
+impl DateTime {
+    pub fn date(&self) -> Date {
+        self.date
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = civil_from_days(self.days);
+
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.date)?;
+
+        if self.seconds != 0 {
+            write!(
+                f,
+                " {:02}:{:02}",
+                self.seconds / 3600,
+                self.seconds % 3600 / 60
+            )?;
+        }
+        if self.seconds % 60 != 0 {
+            write!(f, ":{:02}", self.seconds % 60)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn seconds_from_days(days: &Decimal) -> Decimal {
+    precise(days).mul(Decimal::from(SECONDS_PER_DAY))
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test_date {
+    use super::*;
+
+    fn date(value: u32) -> Date {
+        Date::from_value(&Decimal::from(value)).unwrap()
+    }
+
+    #[test]
+    fn parses_and_formats_dates() {
+        assert_eq!(date(20240229).to_string(), "2024-02-29");
+        assert_eq!(date(19691231).to_value(), dbig!(19691231));
+        assert!(Date::from_value(&dbig!(20230229)).is_err());
+        assert!(Date::from_value(&dbig!(20241301)).is_err());
+        assert!(Date::from_value(&dbig!(2024.5)).is_err());
+    }
+
+    #[test]
+    fn finds_weekdays() {
+        assert_eq!(date(19700101).weekday(), 3);
+        assert!(date(20250214).is_business_day());
+        assert!(!date(20250215).is_business_day());
+    }
+
+    #[test]
+    fn counts_days_and_weeks() {
+        assert_eq!(date(20240101).days_until(date(20250101)), 366);
+        assert_eq!(date(20250115).days_until(date(20250101)), -14);
+        assert_eq!(date(20250101).weeks_until(date(20250115)), dbig!(2));
+    }
+
+    #[test]
+    fn counts_whole_months() {
+        assert_eq!(date(20250115).months_until(date(20250314)), 1);
+        assert_eq!(date(20250115).months_until(date(20250315)), 2);
+        assert_eq!(date(20250131).months_until(date(20250228)), 1);
+        assert_eq!(date(20250315).months_until(date(20250115)), -2);
+    }
+
+    #[test]
+    fn counts_business_days() {
+        assert_eq!(date(20250214).business_days_until(date(20250217)), 1);
+        assert_eq!(date(20250203).business_days_until(date(20250217)), 10);
+        assert_eq!(date(20250217).business_days_until(date(20250214)), -1);
+        assert_eq!(date(20250215).business_days_until(date(20250216)), 0);
+    }
+
+    #[test]
+    fn shifts_by_seconds() {
+        assert_eq!(
+            date(20250228)
+                .shift(&seconds_from_days(&dbig!(1)))
+                .unwrap()
+                .to_string(),
+            "2025-03-01"
+        );
+        assert_eq!(
+            date(20250301).shift(&dbig!(-5400)).unwrap().to_string(),
+            "2025-02-28 22:30"
+        );
+        assert_eq!(
+            date(20250301).shift(&dbig!(45)).unwrap().date(),
+            date(20250301)
+        );
+        assert!(date(99991231).shift(&seconds_from_days(&dbig!(1))).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use super::library::{FunctionDefinition, Library};
+use crate::{action_button::action_button, calculator::action::CalculatorAction, paths};
+use dashu::Decimal;
+use gpui::{
+    div, prelude::*, px, rgb, App, Context, Entity, EventEmitter, Global, SharedString, Window,
+};
+
+// One library for every tab and window, so saving from one never overwrites
+// definitions added in another.
+pub struct SharedLibrary {
+    library: Library,
+    // Failing to load or save the file is shown by every view.
+    error: Option<SharedString>,
+}
+
+struct GlobalLibrary(Entity<SharedLibrary>);
+
+impl Global for GlobalLibrary {}
+
+pub struct DefinitionsView {
+    shared: Entity<SharedLibrary>,
+    recording: bool,
+    // Errors from a definition pasted into this view.
+    error: Option<SharedString>,
+}
+
+impl SharedLibrary {
+    fn save(&mut self, cx: &mut Context<Self>) {
+        self.error = self
+            .library
+            .save(&paths::definitions_file())
+            .err()
+            .map(|err| err.to_string().into());
+        cx.notify();
+    }
+}
+
+// Loads the definitions file the first time it is needed.
+pub fn shared_library(cx: &mut App) -> Entity<SharedLibrary> {
+    if let Some(GlobalLibrary(shared)) = cx.try_global::<GlobalLibrary>() {
+        return shared.clone();
+    }
+
+    let (library, error) = match Library::load(&paths::definitions_file()) {
+        Ok(library) => (library, None),
+        Err(err) => (Library::default(), Some(err.to_string().into())),
+    };
+    let shared = cx.new(|_| SharedLibrary { library, error });
+    cx.set_global(GlobalLibrary(shared.clone()));
+
+    shared
+}
+
+pub fn key_bindings(cx: &mut App) -> Vec<(String, usize)> {
+    shared_library(cx).read(cx).library.key_bindings()
+}
+
+impl DefinitionsView {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let shared = shared_library(cx);
+        cx.observe(&shared, |_, _, cx| cx.notify()).detach();
+
+        DefinitionsView {
+            shared,
+            recording: false,
+            error: None,
+        }
+    }
+
+    pub fn set_recording(&mut self, recording: bool, cx: &mut Context<Self>) {
+        self.recording = recording;
+        cx.notify();
+    }
+
+    // Returns false when the text is not a definition at all, so paste can
+    // fall through to other handling.
+    pub fn define(&mut self, text: &str, cx: &mut Context<Self>) -> bool {
+        if !text.contains('=') {
+            return false;
+        }
+
+        self.error = match FunctionDefinition::parse(text.trim()) {
+            Ok(definition) => {
+                self.shared.update(cx, |shared, cx| {
+                    shared.library.define(definition);
+                    shared.save(cx);
+                });
+                None
+            }
+            Err(err) => Some(err.to_string().into()),
+        };
+
+        cx.notify();
+        true
+    }
+
+    pub fn record(
+        &mut self,
+        actions: Vec<CalculatorAction>,
+        cx: &mut Context<Self>,
+    ) -> Option<(String, usize)> {
+        self.shared.update(cx, |shared, cx| {
+            let binding = shared
+                .library
+                .record(actions)
+                .and_then(|definition| definition.key.clone())
+                .map(|key| (key, shared.library.macros.len() - 1));
+
+            shared.save(cx);
+
+            binding
+        })
+    }
+
+    pub fn apply(&self, index: usize, value: &Decimal, cx: &App) -> Option<Decimal> {
+        self.shared.read(cx).library.apply(index, value)
+    }
+
+    pub fn actions(&self, index: usize, cx: &App) -> Vec<CalculatorAction> {
+        self.shared.read(cx).library.actions(index)
+    }
+}
+
+impl Render for DefinitionsView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let shared = self.shared.read(cx);
+        let error = self.error.clone().or_else(|| shared.error.clone());
+        let library = shared.library.clone();
+
+        let functions: Vec<_> = library
+            .functions
+            .clone()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, definition)| !definition.hidden)
+            .map(|(index, definition)| {
+                let label = format!("{}(x)", definition.name);
+                action_button(("function", index), label, None, cx, move |_, cx| {
+                    cx.emit(Event::Apply(index))
+                })
+            })
+            .collect();
+        let macros: Vec<_> = library
+            .macros
+            .clone()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, definition)| !definition.hidden)
+            .map(|(index, definition)| {
+                action_button(("macro", index), definition.name, None, cx, move |_, cx| {
+                    cx.emit(Event::Run(index))
+                })
+            })
+            .collect();
+        let record_color = self.recording.then(|| rgb(0xff6060));
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.))
+            .px(px(5.))
+            .text_xs()
+            .text_color(rgb(0xcccccc))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .flex_wrap()
+                    .gap(px(3.))
+                    .children(functions)
+                    .children(macros)
+                    .child(action_button("record", "●", record_color, cx, |_, cx| {
+                        cx.emit(Event::Record)
+                    })),
+            )
+            .when_some(error, |this, error| {
+                this.child(div().text_color(rgb(0xff6060)).child(error))
+            })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Apply(usize),
+    Run(usize),
+    Record,
+}
+
+impl EventEmitter<Event> for DefinitionsView {}
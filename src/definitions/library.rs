@@ -0,0 +1,254 @@
+use crate::calculator::{action::CalculatorAction, expression::Expression};
+use anyhow::{anyhow, Result};
+use dashu::Decimal;
+use std::{fs, path::Path};
+
+const MACRO_KEYS: usize = 9;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionDefinition {
+    pub name: String,
+    pub body: String,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub key: Option<String>,
+    pub actions: Vec<CalculatorAction>,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Library {
+    #[serde(default)]
+    pub functions: Vec<FunctionDefinition>,
+    #[serde(default)]
+    pub macros: Vec<MacroDefinition>,
+}
+
+impl FunctionDefinition {
+    pub fn parse(text: &str) -> Result<FunctionDefinition> {
+        let (head, body) = text
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Expected a definition like f(x) = x^2"))?;
+        let (name, parameter) = head
+            .trim()
+            .strip_suffix(')')
+            .and_then(|head| head.split_once('('))
+            .ok_or_else(|| anyhow!("Expected a definition like f(x) = x^2"))?;
+
+        let name = name.trim();
+        if name.is_empty() || !name.chars().all(char::is_alphanumeric) {
+            return Err(anyhow!("Invalid function name {}", name));
+        }
+
+        if parameter.trim() != "x" {
+            return Err(anyhow!("Functions take a single parameter x"));
+        }
+
+        let definition = FunctionDefinition {
+            name: name.to_string(),
+            body: body.trim().to_string(),
+            hidden: false,
+        };
+        definition.expression()?;
+
+        Ok(definition)
+    }
+
+    pub fn expression(&self) -> Result<Expression> {
+        Expression::parse(&self.body)
+    }
+}
+
+impl Library {
+    pub fn parse(json: &str) -> Result<Library> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Library> {
+        if !path.exists() {
+            return Ok(Library::default());
+        }
+
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    pub fn define(&mut self, definition: FunctionDefinition) {
+        match self
+            .functions
+            .iter_mut()
+            .find(|function| function.name == definition.name)
+        {
+            Some(function) => *function = definition,
+            None => self.functions.push(definition),
+        }
+    }
+
+    pub fn apply(&self, index: usize, value: &Decimal) -> Option<Decimal> {
+        self.functions
+            .get(index)?
+            .expression()
+            .ok()?
+            .evaluate(value)
+    }
+
+    // New macros are named M1, M2, … and bound to alt-1 … alt-9 while free.
+    pub fn record(&mut self, actions: Vec<CalculatorAction>) -> Option<&MacroDefinition> {
+        if actions.is_empty() {
+            return None;
+        }
+
+        let number = (1..)
+            .find(|number| {
+                let name = format!("M{}", number);
+                !self.macros.iter().any(|definition| definition.name == name)
+            })
+            .unwrap();
+        let key = Some(format!("alt-{}", number)).filter(|key| {
+            number <= MACRO_KEYS && !self.macros.iter().any(|m| m.key.as_ref() == Some(key))
+        });
+
+        self.macros.push(MacroDefinition {
+            name: format!("M{}", number),
+            key,
+            actions,
+            hidden: false,
+        });
+
+        self.macros.last()
+    }
+
+    pub fn key_bindings(&self) -> Vec<(String, usize)> {
+        self.macros
+            .iter()
+            .enumerate()
+            .filter_map(|(index, definition)| Some((definition.key.clone()?, index)))
+            .collect()
+    }
+
+    // Nested macros and recording toggles are dropped so replay always ends.
+    pub fn actions(&self, index: usize) -> Vec<CalculatorAction> {
+        self.macros
+            .get(index)
+            .map(|definition| {
+                definition
+                    .actions
+                    .iter()
+                    .filter(|action| {
+                        !matches!(
+                            action,
+                            CalculatorAction::RunMacro(_) | CalculatorAction::ToggleRecording
+                        )
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test_library {
+    use super::*;
+    use crate::calculator::calculation::Operation;
+
+    #[test]
+    fn parses_function_definitions() {
+        let definition = FunctionDefinition::parse("f(x) = x^2 + 2x").unwrap();
+
+        assert_eq!(definition.name, "f");
+        assert_eq!(definition.body, "x^2 + 2x");
+        assert!(FunctionDefinition::parse("x^2").is_err());
+        assert!(FunctionDefinition::parse("f(t) = t^2").is_err());
+        assert!(FunctionDefinition::parse("f(x) = x +").is_err());
+        assert!(FunctionDefinition::parse("(x) = x").is_err());
+    }
+
+    #[test]
+    fn applies_and_redefines_functions() {
+        let mut library = Library::default();
+        library.define(FunctionDefinition::parse("f(x) = x^2 + 2x").unwrap());
+        assert_eq!(library.apply(0, &dbig!(3)), Some(dbig!(15)));
+
+        library.define(FunctionDefinition::parse("f(x) = x / 2").unwrap());
+        assert_eq!(library.functions.len(), 1);
+        assert_eq!(library.apply(0, &dbig!(3)), Some(dbig!(1.5)));
+        assert_eq!(library.apply(1, &dbig!(3)), None);
+    }
+
+    #[test]
+    fn records_macros_with_free_names_and_keys() {
+        let mut library = Library::default();
+        assert!(library.record(vec![]).is_none());
+
+        let recorded = library
+            .record(vec![
+                CalculatorAction::Numeric(2),
+                CalculatorAction::Calculate,
+            ])
+            .unwrap();
+        assert_eq!(recorded.name, "M1");
+        assert_eq!(recorded.key.as_deref(), Some("alt-1"));
+
+        library.macros[0].name = "Double".into();
+        let recorded = library.record(vec![CalculatorAction::Undo]).unwrap();
+        assert_eq!(recorded.name, "M1");
+        assert_eq!(recorded.key, None);
+    }
+
+    #[test]
+    fn replays_without_nested_macros() {
+        let mut library = Library::default();
+        library.record(vec![
+            CalculatorAction::Op(Operation::Multiplication),
+            CalculatorAction::RunMacro(0),
+            CalculatorAction::Numeric(2),
+        ]);
+
+        assert_eq!(
+            library.actions(0),
+            vec![
+                CalculatorAction::Op(Operation::Multiplication),
+                CalculatorAction::Numeric(2)
+            ]
+        );
+        assert!(library.actions(1).is_empty());
+    }
+
+    #[test]
+    fn lists_key_bindings() {
+        let mut library = Library::default();
+        library.record(vec![CalculatorAction::Numeric(1)]);
+        library.record(vec![CalculatorAction::Numeric(2)]);
+        library.macros[0].key = None;
+
+        assert_eq!(library.key_bindings(), vec![("alt-2".to_string(), 1)]);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut library = Library::default();
+        library.define(FunctionDefinition::parse("sq(x) = x^2").unwrap());
+        library.record(vec![CalculatorAction::Numeric(1)]);
+
+        let json = serde_json::to_string(&library).unwrap();
+        assert_eq!(Library::parse(&json).unwrap(), library);
+        assert_eq!(Library::parse("{}").unwrap(), Library::default());
+    }
+}
@@ -0,0 +1,306 @@
+use super::{
+    pricing::{compound_interest, margin, markup_price, percent_change},
+    tvm::{amortization_csv, Tvm, TvmVariable},
+};
+use crate::{
+    action_button::action_button,
+    calculator::numeric_value::{round_to_places, NumericValue},
+    paths,
+};
+use anyhow::Result;
+use dashu::Decimal;
+use gpui::{div, prelude::*, px, rgb, Context, EventEmitter, SharedString, Window};
+use std::fs;
+
+const PLACES: [usize; 3] = [2, 0, 4];
+const COMPOUNDING: [usize; 4] = [12, 4, 1, 365];
+
+pub struct FinanceView {
+    tvm: Tvm,
+    input: NumericValue,
+    first: Decimal,
+    second: Decimal,
+    places: usize,
+    compounding: usize,
+    error: Option<SharedString>,
+}
+
+impl FinanceView {
+    pub fn new() -> Self {
+        FinanceView {
+            tvm: Tvm::default(),
+            input: NumericValue::default(),
+            first: Decimal::ZERO,
+            second: Decimal::ZERO,
+            places: 0,
+            compounding: 0,
+            error: None,
+        }
+    }
+
+    pub fn set_input(&mut self, input: NumericValue) {
+        self.input = input;
+    }
+
+    fn places(&self) -> usize {
+        PLACES[self.places]
+    }
+
+    fn rounded(&self, value: &Decimal) -> NumericValue {
+        NumericValue::new(round_to_places(value, self.places()))
+    }
+
+    fn store(&mut self, variable: TvmVariable, cx: &mut Context<Self>) {
+        self.tvm.set(variable, self.input.val().clone());
+        self.error = None;
+        cx.notify();
+    }
+
+    fn compute(&mut self, variable: TvmVariable, cx: &mut Context<Self>) {
+        match self.tvm.solve(variable) {
+            Ok(value) => {
+                self.tvm.set(variable, value.clone());
+                self.error = None;
+                cx.emit(Event::Insert(self.rounded(&value)));
+            }
+            Err(err) => self.error = Some(err.to_string().into()),
+        }
+
+        cx.notify();
+    }
+
+    fn store_first(&mut self, cx: &mut Context<Self>) {
+        self.first = self.input.val().clone();
+        cx.notify();
+    }
+
+    fn store_second(&mut self, cx: &mut Context<Self>) {
+        self.second = self.input.val().clone();
+        cx.notify();
+    }
+
+    fn next_places(&mut self, cx: &mut Context<Self>) {
+        self.places = (self.places + 1) % PLACES.len();
+        cx.notify();
+    }
+
+    fn next_compounding(&mut self, cx: &mut Context<Self>) {
+        self.compounding = (self.compounding + 1) % COMPOUNDING.len();
+        cx.notify();
+    }
+
+    fn export(&mut self, cx: &mut Context<Self>) {
+        let rows = match self.tvm.amortization(self.places()) {
+            Ok(rows) => rows,
+            Err(err) => {
+                self.error = Some(err.to_string().into());
+                cx.notify();
+                return;
+            }
+        };
+
+        let contents = amortization_csv(&rows);
+        let directory = dirs::document_dir().unwrap_or_else(paths::config_dir);
+        let path = cx.prompt_for_new_path(&directory);
+
+        cx.spawn(|this, mut cx| async move {
+            let error = match path.await {
+                Ok(Ok(Some(path))) => {
+                    let path = path.with_extension("csv");
+
+                    fs::write(&path, contents).err().map(|err| {
+                        format!(
+                            "Failed to export amortization to {}: {}",
+                            path.display(),
+                            err
+                        )
+                    })
+                }
+                Ok(Err(err)) => Some(format!("Failed to choose a file: {}", err)),
+                _ => None,
+            };
+
+            this.update(&mut cx, |this, cx| {
+                this.error = error.map(Into::into);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn helpers(&self) -> Vec<(SharedString, Result<Decimal>)> {
+        vec![
+            ("%Δ A→B".into(), percent_change(&self.first, &self.second)),
+            (
+                "A +B% markup".into(),
+                Ok(markup_price(&self.first, &self.second)),
+            ),
+            ("margin A→B %".into(), margin(&self.first, &self.second)),
+            (
+                format!("PV compounded ×{}", COMPOUNDING[self.compounding]).into(),
+                compound_interest(
+                    &self.tvm.present_value,
+                    &self.tvm.rate,
+                    COMPOUNDING[self.compounding],
+                    &self.tvm.periods,
+                ),
+            ),
+        ]
+    }
+
+    fn render_variable(
+        &self,
+        index: usize,
+        variable: TvmVariable,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let value = self.rounded(self.tvm.get(variable));
+
+        div()
+            .w_full()
+            .flex()
+            .flex_row()
+            .justify_between()
+            .gap(px(4.))
+            .child(
+                div()
+                    .id(("store", index))
+                    .w(px(28.))
+                    .child(variable.to_string())
+                    .on_click(cx.listener(move |this, _, _, cx| this.store(variable, cx))),
+            )
+            .child(
+                div()
+                    .id(("value", index))
+                    .flex_grow()
+                    .flex()
+                    .justify_end()
+                    .overflow_hidden()
+                    .text_color(rgb(0xffffff))
+                    .child(value.to_string())
+                    .on_click(
+                        cx.listener(move |_, _, _, cx| cx.emit(Event::Insert(value.clone()))),
+                    ),
+            )
+            .child(
+                div()
+                    .id(("compute", index))
+                    .px(px(3.))
+                    .rounded_sm()
+                    .bg(rgb(0xff9600))
+                    .child("CPT")
+                    .on_click(cx.listener(move |this, _, _, cx| this.compute(variable, cx))),
+            )
+    }
+
+    fn render_helper(
+        &self,
+        index: usize,
+        label: SharedString,
+        result: Result<Decimal>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let value = result.ok().map(|value| self.rounded(&value));
+        let text: SharedString = match &value {
+            Some(value) => value.to_string().into(),
+            None => "–".into(),
+        };
+
+        div()
+            .id(("helper", index))
+            .w_full()
+            .flex()
+            .flex_row()
+            .justify_between()
+            .child(label)
+            .child(div().text_color(rgb(0xffffff)).child(text))
+            .on_click(cx.listener(move |_, _, _, cx| {
+                if let Some(value) = &value {
+                    cx.emit(Event::Insert(value.clone()));
+                }
+            }))
+    }
+}
+
+impl Default for FinanceView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for FinanceView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let variables: Vec<_> = TvmVariable::ALL
+            .into_iter()
+            .enumerate()
+            .map(|(index, variable)| self.render_variable(index, variable, cx))
+            .collect();
+        let helpers: Vec<_> = self
+            .helpers()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (label, result))| self.render_helper(index, label, result, cx))
+            .collect();
+
+        let places: SharedString = format!("{:.*}", self.places(), 0.0).into();
+        let first: SharedString = format!("A {}", self.rounded(&self.first)).into();
+        let second: SharedString = format!("B {}", self.rounded(&self.second)).into();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.))
+            .px(px(5.))
+            .text_xs()
+            .text_color(rgb(0xcccccc))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .gap(px(3.))
+                    .child(action_button("places", places, None, cx, |this, cx| {
+                        this.next_places(cx)
+                    }))
+                    .child(action_button(
+                        "compounding",
+                        format!("×{}/yr", COMPOUNDING[self.compounding]),
+                        None,
+                        cx,
+                        |this, cx| this.next_compounding(cx),
+                    ))
+                    .child(action_button(
+                        "amortization",
+                        "Amort CSV",
+                        None,
+                        cx,
+                        |this, cx| this.export(cx),
+                    )),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_color(rgb(0xff6060)).child(error))
+            })
+            .children(variables)
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .gap(px(3.))
+                    .child(action_button("first", first, None, cx, |this, cx| {
+                        this.store_first(cx)
+                    }))
+                    .child(action_button("second", second, None, cx, |this, cx| {
+                        this.store_second(cx)
+                    })),
+            )
+            .children(helpers)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Insert(NumericValue),
+}
+
+impl EventEmitter<Event> for FinanceView {}
@@ -0,0 +1,3 @@
+pub mod component;
+pub mod pricing;
+pub mod tvm;
@@ -0,0 +1,120 @@
+use crate::calculator::numeric_value::{precise, PRECISION};
+use anyhow::{anyhow, Result};
+use dashu::Decimal;
+use std::ops::{Add, Div, Mul, Sub};
+
+fn hundred() -> Decimal {
+    Decimal::from(100u8)
+}
+
+pub fn markup_price(cost: &Decimal, markup: &Decimal) -> Decimal {
+    precise(cost)
+        .mul(precise(markup).div(hundred()).add(Decimal::ONE))
+        .with_precision(PRECISION)
+        .value()
+}
+
+pub fn margin(cost: &Decimal, price: &Decimal) -> Result<Decimal> {
+    if *price == Decimal::ZERO {
+        return Err(anyhow!("Price must not be zero"));
+    }
+
+    Ok(precise(price)
+        .sub(cost)
+        .div(price)
+        .mul(hundred())
+        .with_precision(PRECISION)
+        .value())
+}
+
+pub fn percent_change(from: &Decimal, to: &Decimal) -> Result<Decimal> {
+    if *from == Decimal::ZERO {
+        return Err(anyhow!("Starting value must not be zero"));
+    }
+
+    Ok(precise(to)
+        .sub(from)
+        .div(from)
+        .mul(hundred())
+        .with_precision(PRECISION)
+        .value())
+}
+
+pub fn compound_interest(
+    principal: &Decimal,
+    annual_rate: &Decimal,
+    periods_per_year: usize,
+    years: &Decimal,
+) -> Result<Decimal> {
+    if periods_per_year == 0 {
+        return Err(anyhow!("Compounding periods must not be zero"));
+    }
+
+    let periods = Decimal::from(periods_per_year);
+    let rate = precise(annual_rate).div(hundred()).div(&periods);
+    let base = precise(&rate).add(Decimal::ONE);
+    // Powers of a base at or below zero are undefined for fractional and
+    // negative exponents.
+    if base <= Decimal::ZERO {
+        return Err(anyhow!("Rate must be greater than -100% per period"));
+    }
+
+    let exponent = precise(years).mul(&periods);
+
+    let growth = if exponent.fract() == Decimal::ZERO {
+        base.powi(exponent.to_int().value())
+    } else {
+        base.powf(&exponent)
+    };
+
+    Ok(precise(principal)
+        .mul(growth)
+        .with_precision(PRECISION)
+        .value())
+}
+
+#[cfg(test)]
+mod test_pricing {
+    use super::*;
+    use crate::calculator::numeric_value::round_to_places;
+
+    #[test]
+    fn applies_markup() {
+        assert_eq!(markup_price(&dbig!(80), &dbig!(25)), dbig!(100));
+    }
+
+    #[test]
+    fn computes_margin() {
+        assert_eq!(margin(&dbig!(80), &dbig!(100)).unwrap(), dbig!(20));
+        assert!(margin(&dbig!(80), &dbig!(0)).is_err());
+    }
+
+    #[test]
+    fn computes_percent_change() {
+        assert_eq!(percent_change(&dbig!(80), &dbig!(100)).unwrap(), dbig!(25));
+        assert_eq!(percent_change(&dbig!(100), &dbig!(80)).unwrap(), dbig!(-20));
+        assert!(percent_change(&dbig!(0), &dbig!(80)).is_err());
+    }
+
+    #[test]
+    fn compounds_interest() {
+        assert_eq!(
+            round_to_places(
+                &compound_interest(&dbig!(1000), &dbig!(5), 12, &dbig!(10)).unwrap(),
+                2
+            ),
+            dbig!(1647.01)
+        );
+        assert_eq!(
+            compound_interest(&dbig!(1000), &dbig!(10), 1, &dbig!(2)).unwrap(),
+            dbig!(1210)
+        );
+    }
+
+    #[test]
+    fn rejects_rates_of_minus_100_percent_or_less() {
+        assert!(compound_interest(&dbig!(1000), &dbig!(-100), 1, &dbig!(-2)).is_err());
+        assert!(compound_interest(&dbig!(1000), &dbig!(-300), 2, &dbig!(1.25)).is_err());
+        assert!(compound_interest(&dbig!(1000), &dbig!(5), 0, &dbig!(1)).is_err());
+    }
+}
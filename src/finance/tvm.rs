@@ -0,0 +1,475 @@
+use crate::calculator::numeric_value::{precise, round_to_places, PRECISION};
+use anyhow::{anyhow, Result};
+use dashu::{base::Abs, integer::IBig, Decimal};
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+const MAX_ITERATIONS: usize = 100;
+const MAX_SCHEDULE_PERIODS: usize = 1200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TvmVariable {
+    Periods,
+    Rate,
+    PresentValue,
+    Payment,
+    FutureValue,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tvm {
+    pub periods: Decimal,
+    pub rate: Decimal,
+    pub present_value: Decimal,
+    pub payment: Decimal,
+    pub future_value: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmortizationRow {
+    pub period: usize,
+    pub payment: Decimal,
+    pub interest: Decimal,
+    pub principal: Decimal,
+    pub balance: Decimal,
+}
+
+impl TvmVariable {
+    pub const ALL: [TvmVariable; 5] = [
+        TvmVariable::Periods,
+        TvmVariable::Rate,
+        TvmVariable::PresentValue,
+        TvmVariable::Payment,
+        TvmVariable::FutureValue,
+    ];
+}
+
+impl Display for TvmVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TvmVariable::Periods => f.write_str("N"),
+            TvmVariable::Rate => f.write_str("I/Y"),
+            TvmVariable::PresentValue => f.write_str("PV"),
+            TvmVariable::Payment => f.write_str("PMT"),
+            TvmVariable::FutureValue => f.write_str("FV"),
+        }
+    }
+}
+
+impl Tvm {
+    pub fn get(&self, variable: TvmVariable) -> &Decimal {
+        match variable {
+            TvmVariable::Periods => &self.periods,
+            TvmVariable::Rate => &self.rate,
+            TvmVariable::PresentValue => &self.present_value,
+            TvmVariable::Payment => &self.payment,
+            TvmVariable::FutureValue => &self.future_value,
+        }
+    }
+
+    pub fn set(&mut self, variable: TvmVariable, value: Decimal) {
+        match variable {
+            TvmVariable::Periods => self.periods = value,
+            TvmVariable::Rate => self.rate = value,
+            TvmVariable::PresentValue => self.present_value = value,
+            TvmVariable::Payment => self.payment = value,
+            TvmVariable::FutureValue => self.future_value = value,
+        }
+    }
+
+    pub fn solve(&self, variable: TvmVariable) -> Result<Decimal> {
+        let rate = self.periodic_rate();
+
+        match variable {
+            TvmVariable::Periods => self.solve_periods(&rate),
+            TvmVariable::Rate => self.solve_rate(),
+            TvmVariable::PresentValue => {
+                let growth = growth(&rate, &self.periods)?;
+                if growth == Decimal::ZERO {
+                    return Err(anyhow!("No solution for PV"));
+                }
+
+                Ok(precise(&self.future_value)
+                    .add(precise(&self.payment).mul(annuity(&rate, &self.periods)?))
+                    .div(growth)
+                    .neg()
+                    .with_precision(PRECISION)
+                    .value())
+            }
+            TvmVariable::Payment => {
+                let annuity = annuity(&rate, &self.periods)?;
+                if annuity == Decimal::ZERO {
+                    return Err(anyhow!("N must not be zero"));
+                }
+
+                Ok(precise(&self.present_value)
+                    .mul(growth(&rate, &self.periods)?)
+                    .add(&self.future_value)
+                    .div(annuity)
+                    .neg()
+                    .with_precision(PRECISION)
+                    .value())
+            }
+            TvmVariable::FutureValue => Ok(precise(&self.present_value)
+                .mul(growth(&rate, &self.periods)?)
+                .add(precise(&self.payment).mul(annuity(&rate, &self.periods)?))
+                .neg()
+                .with_precision(PRECISION)
+                .value()),
+        }
+    }
+
+    pub fn amortization(&self, places: usize) -> Result<Vec<AmortizationRow>> {
+        let periods: usize = self
+            .periods
+            .to_int()
+            .value()
+            .try_into()
+            .map_err(|_| anyhow!("N must be a positive whole number"))?;
+
+        if Decimal::from(periods) != self.periods || periods == 0 {
+            return Err(anyhow!("N must be a positive whole number"));
+        }
+        if periods > MAX_SCHEDULE_PERIODS {
+            return Err(anyhow!("N must be at most {}", MAX_SCHEDULE_PERIODS));
+        }
+
+        let rate = self.periodic_rate();
+        let target = round_to_places(&self.future_value, places).neg();
+        let mut balance = round_to_places(&self.present_value, places);
+
+        Ok((1..=periods)
+            .map(|period| {
+                let interest = round_to_places(&precise(&balance).mul(&rate), places);
+                let payment = if period == periods {
+                    precise(&target).sub(&balance).sub(&interest)
+                } else {
+                    round_to_places(&self.payment, places)
+                };
+                let principal = precise(&payment).add(&interest).neg();
+                balance = precise(&balance).add(&interest).add(&payment);
+
+                AmortizationRow {
+                    period,
+                    payment: payment.clone(),
+                    interest,
+                    principal,
+                    balance: balance.clone(),
+                }
+            })
+            .collect())
+    }
+
+    fn periodic_rate(&self) -> Decimal {
+        precise(&self.rate).div(Decimal::from(100u8))
+    }
+
+    fn residual(&self, rate: &Decimal) -> Result<Decimal> {
+        Ok(precise(&self.present_value)
+            .mul(growth(rate, &self.periods)?)
+            .add(precise(&self.payment).mul(annuity(rate, &self.periods)?))
+            .add(&self.future_value))
+    }
+
+    fn solve_periods(&self, rate: &Decimal) -> Result<Decimal> {
+        if *rate == Decimal::ZERO {
+            if self.payment == Decimal::ZERO {
+                return Err(anyhow!("PMT must not be zero when I/Y is zero"));
+            }
+
+            return Ok(precise(&self.present_value)
+                .add(&self.future_value)
+                .div(&self.payment)
+                .neg()
+                .with_precision(PRECISION)
+                .value());
+        }
+
+        let base = growth_base(rate)?;
+        let numerator = precise(&self.payment).sub(precise(&self.future_value).mul(rate));
+        let denominator = precise(&self.payment).add(precise(&self.present_value).mul(rate));
+
+        if denominator == Decimal::ZERO {
+            return Err(anyhow!("No solution for N"));
+        }
+
+        let ratio = precise(&numerator).div(&denominator);
+        if ratio <= Decimal::ZERO {
+            return Err(anyhow!("No solution for N"));
+        }
+
+        Ok(ratio.ln().div(base.ln()).with_precision(PRECISION).value())
+    }
+
+    fn solve_rate(&self) -> Result<Decimal> {
+        let step = Decimal::from_parts(IBig::ONE, -12);
+        let tolerance = Decimal::from_parts(IBig::ONE, -20);
+        let lower_bound = Decimal::from_parts(IBig::from(-99), -2);
+
+        let mut rate = Decimal::from_parts(IBig::ONE, -2);
+
+        for _ in 0..MAX_ITERATIONS {
+            let value = self.residual(&rate)?;
+            let slope = precise(&self.residual(&precise(&rate).add(&step))?)
+                .sub(&value)
+                .div(&step);
+
+            if slope == Decimal::ZERO {
+                break;
+            }
+
+            let delta = precise(&value).div(&slope);
+            rate = precise(&rate).sub(&delta);
+
+            if rate <= lower_bound {
+                break;
+            }
+
+            if delta.abs() <= tolerance {
+                return Ok(round_to_places(
+                    &precise(&rate).mul(Decimal::from(100u8)),
+                    PRECISION / 2,
+                ));
+            }
+        }
+
+        Err(anyhow!("No solution for I/Y"))
+    }
+}
+
+impl Default for Tvm {
+    fn default() -> Self {
+        Tvm {
+            periods: Decimal::ZERO,
+            rate: Decimal::ZERO,
+            present_value: Decimal::ZERO,
+            payment: Decimal::ZERO,
+            future_value: Decimal::ZERO,
+        }
+    }
+}
+
+// Powers and logarithms of a base at or below zero are undefined, so rates of
+// -100% or less are rejected before they reach dashu.
+fn growth_base(rate: &Decimal) -> Result<Decimal> {
+    let base = precise(rate).add(Decimal::ONE);
+
+    if base <= Decimal::ZERO {
+        return Err(anyhow!("I/Y must be greater than -100%"));
+    }
+
+    Ok(base)
+}
+
+fn growth(rate: &Decimal, periods: &Decimal) -> Result<Decimal> {
+    let base = growth_base(rate)?;
+
+    Ok(if periods.fract() == Decimal::ZERO {
+        base.powi(periods.to_int().value())
+    } else {
+        base.powf(&precise(periods))
+    })
+}
+
+fn annuity(rate: &Decimal, periods: &Decimal) -> Result<Decimal> {
+    if *rate == Decimal::ZERO {
+        return Ok(periods.clone());
+    }
+
+    Ok(growth(rate, periods)?.sub(Decimal::ONE).div(rate))
+}
+
+pub fn amortization_csv(rows: &[AmortizationRow]) -> String {
+    let mut csv = String::from("period,payment,interest,principal,balance\n");
+
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            row.period, row.payment, row.interest, row.principal, row.balance
+        ));
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod test_solve {
+    use super::*;
+
+    fn cents(value: Decimal) -> Decimal {
+        round_to_places(&value, 2)
+    }
+
+    fn mortgage() -> Tvm {
+        Tvm {
+            periods: dbig!(360),
+            rate: dbig!(0.5),
+            present_value: dbig!(200000),
+            payment: dbig!(-1199.10),
+            future_value: dbig!(0),
+        }
+    }
+
+    #[test]
+    fn solves_payment() {
+        assert_eq!(
+            cents(mortgage().solve(TvmVariable::Payment).unwrap()),
+            dbig!(-1199.10)
+        );
+    }
+
+    #[test]
+    fn solves_present_value() {
+        assert_eq!(
+            round_to_places(&mortgage().solve(TvmVariable::PresentValue).unwrap(), 0),
+            dbig!(200000)
+        );
+    }
+
+    #[test]
+    fn solves_future_value_of_savings() {
+        let tvm = Tvm {
+            periods: dbig!(10),
+            rate: dbig!(5),
+            present_value: dbig!(-1000),
+            payment: dbig!(0),
+            future_value: dbig!(0),
+        };
+
+        assert_eq!(
+            cents(tvm.solve(TvmVariable::FutureValue).unwrap()),
+            dbig!(1628.89)
+        );
+    }
+
+    #[test]
+    fn solves_periods() {
+        assert_eq!(
+            round_to_places(&mortgage().solve(TvmVariable::Periods).unwrap(), 0),
+            dbig!(360)
+        );
+    }
+
+    #[test]
+    fn solves_rate() {
+        assert_eq!(
+            round_to_places(&mortgage().solve(TvmVariable::Rate).unwrap(), 3),
+            dbig!(0.5)
+        );
+    }
+
+    #[test]
+    fn solves_without_interest() {
+        let tvm = Tvm {
+            periods: dbig!(10),
+            rate: dbig!(0),
+            present_value: dbig!(1000),
+            payment: dbig!(-100),
+            future_value: dbig!(0),
+        };
+
+        assert_eq!(tvm.solve(TvmVariable::Payment).unwrap(), dbig!(-100));
+        assert_eq!(tvm.solve(TvmVariable::Periods).unwrap(), dbig!(10));
+    }
+
+    #[test]
+    fn fails_without_periods() {
+        assert!(Tvm::default().solve(TvmVariable::Payment).is_err());
+    }
+
+    #[test]
+    fn rejects_rates_of_minus_100_percent_or_less() {
+        for rate in [dbig!(-100), dbig!(-250)] {
+            let tvm = Tvm { rate, ..mortgage() };
+
+            for variable in TvmVariable::ALL {
+                if variable != TvmVariable::Rate {
+                    assert!(tvm.solve(variable).is_err(), "{}", variable);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_fractional_periods_with_a_negative_base() {
+        let tvm = Tvm {
+            periods: dbig!(2.5),
+            rate: dbig!(-150),
+            ..mortgage()
+        };
+
+        assert!(tvm.solve(TvmVariable::FutureValue).is_err());
+        assert!(tvm.solve(TvmVariable::PresentValue).is_err());
+    }
+
+    #[test]
+    fn solves_fractional_periods() {
+        let tvm = Tvm {
+            periods: dbig!(2.5),
+            rate: dbig!(10),
+            present_value: dbig!(-1000),
+            payment: dbig!(0),
+            future_value: dbig!(0),
+        };
+
+        assert_eq!(
+            cents(tvm.solve(TvmVariable::FutureValue).unwrap()),
+            dbig!(1269.06)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_amortization {
+    use super::*;
+
+    fn loan() -> Tvm {
+        Tvm {
+            periods: dbig!(3),
+            rate: dbig!(1),
+            present_value: dbig!(1000),
+            payment: dbig!(-340.02),
+            future_value: dbig!(0),
+        }
+    }
+
+    #[test]
+    fn builds_schedule() {
+        let rows = loan().amortization(2).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            AmortizationRow {
+                period: 1,
+                payment: dbig!(-340.02),
+                interest: dbig!(10),
+                principal: dbig!(330.02),
+                balance: dbig!(669.98),
+            }
+        );
+        assert_eq!(rows[2].payment, dbig!(-340.03));
+        assert_eq!(rows[2].balance, dbig!(0));
+    }
+
+    #[test]
+    fn rejects_fractional_periods() {
+        let tvm = Tvm {
+            periods: dbig!(2.5),
+            ..loan()
+        };
+
+        assert!(tvm.amortization(2).is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = amortization_csv(&loan().amortization(2).unwrap());
+
+        assert!(csv.starts_with(
+            "period,payment,interest,principal,balance\n1,-340.02,10,330.02,669.98\n"
+        ));
+    }
+}
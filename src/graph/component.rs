@@ -0,0 +1,371 @@
+use super::plot::{decimal, Mark, Plot, Viewport};
+use crate::{
+    action_button::action_button,
+    calculator::{expression::Expression, numeric_value::NumericValue},
+};
+use gpui::{
+    canvas, div, fill, point, prelude::*, px, rgb, size, Bounds, Context, EventEmitter,
+    MouseMoveEvent, Pixels, Point, ScrollWheelEvent, SharedString, Window,
+};
+
+const PLOT_HEIGHT: f32 = 140.;
+const PAN_STEP: f64 = 0.25;
+const ZOOM_STEP: f64 = 0.8;
+const AXIS_COLOR: u32 = 0x707070;
+const MARKER_COLOR: u32 = 0xffffff;
+const COLORS: [u32; 4] = [0xff9600, 0x4fc3f7, 0x9ccc65, 0xf06292];
+
+pub struct GraphView {
+    functions: Vec<(SharedString, Expression)>,
+    viewport: Viewport,
+    bounds: Option<Bounds<Pixels>>,
+    plot: Option<Plot>,
+    trace: Option<usize>,
+    error: Option<SharedString>,
+}
+
+impl GraphView {
+    pub fn new() -> Self {
+        GraphView {
+            functions: vec![],
+            viewport: Viewport::default(),
+            bounds: None,
+            plot: None,
+            trace: None,
+            error: None,
+        }
+    }
+
+    pub fn paste(&mut self, text: &str, cx: &mut Context<Self>) {
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if self.functions.len() == COLORS.len() {
+                self.error = Some(format!("At most {} functions", COLORS.len()).into());
+                break;
+            }
+
+            // Accept "y = …" and "f(x) = …" as well as a bare expression.
+            let body = line.split_once('=').map_or(line, |(_, body)| body).trim();
+
+            match Expression::parse(body) {
+                Ok(expression) => {
+                    self.functions.push((body.to_string().into(), expression));
+                    self.error = None;
+                }
+                Err(err) => {
+                    self.error = Some(err.to_string().into());
+                    break;
+                }
+            }
+        }
+
+        self.plot = None;
+        cx.notify();
+    }
+
+    fn paste_clipboard(&mut self, cx: &mut Context<Self>) {
+        if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+            self.paste(&text, cx);
+        }
+    }
+
+    fn remove(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.functions.remove(index);
+        self.plot = None;
+        cx.notify();
+    }
+
+    fn pan(&mut self, x: f64, y: f64, cx: &mut Context<Self>) {
+        self.viewport.pan(x, y);
+        cx.notify();
+    }
+
+    fn zoom(&mut self, factor: f64, cx: &mut Context<Self>) {
+        let (x, y) = self.viewport.center();
+        self.viewport.zoom(factor, x, y);
+        cx.notify();
+    }
+
+    fn reset(&mut self, cx: &mut Context<Self>) {
+        self.viewport = Viewport::default();
+        cx.notify();
+    }
+
+    fn dimensions(&self) -> Option<(usize, f64)> {
+        self.bounds.map(|bounds| {
+            (
+                f32::from(bounds.size.width).max(1.) as usize,
+                f32::from(bounds.size.height) as f64,
+            )
+        })
+    }
+
+    fn position(&self, position: Point<Pixels>) -> Option<(f64, f64)> {
+        let bounds = self.bounds?;
+
+        Some((
+            f32::from(position.x - bounds.origin.x) as f64,
+            f32::from(position.y - bounds.origin.y) as f64,
+        ))
+    }
+
+    fn refresh(&mut self) {
+        let Some((width, height)) = self.dimensions() else {
+            return;
+        };
+
+        let current = self
+            .plot
+            .as_ref()
+            .is_some_and(|plot| plot.is_current(&self.viewport, width, height));
+
+        if !current {
+            let functions: Vec<Expression> = self
+                .functions
+                .iter()
+                .map(|(_, expression)| expression.clone())
+                .collect();
+
+            self.plot = Some(Plot::new(&functions, self.viewport, width, height));
+        }
+    }
+
+    fn on_mouse_move(&mut self, event: &MouseMoveEvent, cx: &mut Context<Self>) {
+        let Some(((x, _), (width, _))) = self.position(event.position).zip(self.dimensions())
+        else {
+            return;
+        };
+
+        let column = Some((x.round().max(0.) as usize).min(width));
+        if self.trace != column {
+            self.trace = column;
+            cx.notify();
+        }
+    }
+
+    fn on_scroll(&mut self, event: &ScrollWheelEvent, cx: &mut Context<Self>) {
+        let Some(((x, y), (width, height))) = self.position(event.position).zip(self.dimensions())
+        else {
+            return;
+        };
+
+        let delta = f32::from(event.delta.pixel_delta(px(PLOT_HEIGHT / 10.)).y);
+        let factor = match delta {
+            delta if delta > 0. => ZOOM_STEP,
+            delta if delta < 0. => 1. / ZOOM_STEP,
+            _ => return,
+        };
+
+        self.viewport.zoom(
+            factor,
+            self.viewport.world_x(x, width as f64),
+            self.viewport.world_y(y, height),
+        );
+        cx.notify();
+    }
+
+    fn color(mark: Mark) -> u32 {
+        match mark {
+            Mark::Axis => AXIS_COLOR,
+            Mark::Curve(index) | Mark::Trace(index) => COLORS[index % COLORS.len()],
+            Mark::Root(_) | Mark::Intersection(_, _) => MARKER_COLOR,
+        }
+    }
+
+    fn results(&self) -> Vec<(SharedString, NumericValue)> {
+        let mut results = vec![];
+
+        if let Some((column, (width, _))) = self.trace.zip(self.dimensions()) {
+            let x = decimal(self.viewport.world_x(column as f64, width as f64));
+
+            results.push(("x".into(), NumericValue::new(x.clone())));
+            results.extend(self.functions.iter().enumerate().filter_map(
+                |(index, (_, expression))| {
+                    let y = expression.evaluate(&x)?;
+                    Some((format!("f{}(x)", index + 1).into(), NumericValue::new(y)))
+                },
+            ));
+        }
+
+        if let Some(plot) = &self.plot {
+            results.extend(plot.points().iter().map(|point| {
+                let label = match point.mark {
+                    Mark::Intersection(first, second) => format!("f{}∩f{}", first + 1, second + 1),
+                    Mark::Root(index) => format!("f{} root", index + 1),
+                    _ => String::new(),
+                };
+
+                (label.into(), NumericValue::new(decimal(point.x)))
+            }));
+        }
+
+        results
+    }
+
+    fn render_function(&self, index: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id(("function", index))
+            .px(px(3.))
+            .rounded_sm()
+            .bg(rgb(0x3a3a3a))
+            .text_color(rgb(COLORS[index % COLORS.len()]))
+            .child(self.functions[index].0.clone())
+            .on_click(cx.listener(move |this, _, _, cx| this.remove(index, cx)))
+    }
+
+    fn render_plot(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let scene = self
+            .plot
+            .as_ref()
+            .map(|plot| plot.scene(self.trace))
+            .unwrap_or_default();
+
+        div()
+            .id("plot")
+            .w_full()
+            .h(px(PLOT_HEIGHT))
+            .rounded_sm()
+            .bg(rgb(0x3a3a3a))
+            .overflow_hidden()
+            .on_mouse_move(cx.listener(|this, event, _, cx| this.on_mouse_move(event, cx)))
+            .on_scroll_wheel(cx.listener(|this, event, _, cx| this.on_scroll(event, cx)))
+            .on_hover(cx.listener(|this, hovered, _, cx| {
+                if !hovered {
+                    this.trace = None;
+                    cx.notify();
+                }
+            }))
+            .child(
+                canvas(
+                    move |bounds, _, cx| {
+                        view.update(cx, |this, cx| {
+                            if this.bounds != Some(bounds) {
+                                this.bounds = Some(bounds);
+                                cx.notify();
+                            }
+                        })
+                    },
+                    move |bounds, _, window, _| {
+                        for quad in scene {
+                            let origin = point(
+                                bounds.origin.x + px(quad.x as f32),
+                                bounds.origin.y + px(quad.y as f32),
+                            );
+                            let extent = size(px(quad.width as f32), px(quad.height as f32));
+
+                            window.paint_quad(fill(
+                                Bounds::new(origin, extent),
+                                rgb(Self::color(quad.mark)),
+                            ));
+                        }
+                    },
+                )
+                .size_full(),
+            )
+    }
+
+    fn render_result(
+        &self,
+        index: usize,
+        label: SharedString,
+        value: NumericValue,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(("result", index))
+            .w_full()
+            .flex()
+            .flex_row()
+            .justify_between()
+            .child(label)
+            .child(
+                div()
+                    .overflow_hidden()
+                    .text_color(rgb(0xffffff))
+                    .child(value.to_string()),
+            )
+            .on_click(cx.listener(move |_, _, _, cx| cx.emit(Event::Insert(value.clone()))))
+    }
+}
+
+impl Default for GraphView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for GraphView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.refresh();
+
+        let functions: Vec<_> = (0..self.functions.len())
+            .map(|index| self.render_function(index, cx))
+            .collect();
+        let results: Vec<_> = self
+            .results()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (label, value))| self.render_result(index, label, value, cx))
+            .collect();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.))
+            .px(px(5.))
+            .text_xs()
+            .text_color(rgb(0xcccccc))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .flex_wrap()
+                    .gap(px(3.))
+                    .child(action_button("paste", "Paste", None, cx, |this, cx| {
+                        this.paste_clipboard(cx)
+                    }))
+                    .child(action_button("left", "←", None, cx, |this, cx| {
+                        this.pan(-PAN_STEP, 0., cx)
+                    }))
+                    .child(action_button("right", "→", None, cx, |this, cx| {
+                        this.pan(PAN_STEP, 0., cx)
+                    }))
+                    .child(action_button("up", "↑", None, cx, |this, cx| {
+                        this.pan(0., PAN_STEP, cx)
+                    }))
+                    .child(action_button("down", "↓", None, cx, |this, cx| {
+                        this.pan(0., -PAN_STEP, cx)
+                    }))
+                    .child(action_button("zoom_in", "+", None, cx, |this, cx| {
+                        this.zoom(ZOOM_STEP, cx)
+                    }))
+                    .child(action_button("zoom_out", "−", None, cx, |this, cx| {
+                        this.zoom(1. / ZOOM_STEP, cx)
+                    }))
+                    .child(action_button("reset", "⌂", None, cx, |this, cx| {
+                        this.reset(cx)
+                    })),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .flex_wrap()
+                    .gap(px(2.))
+                    .children(functions),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_color(rgb(0xff6060)).child(error))
+            })
+            .child(self.render_plot(cx))
+            .children(results)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Insert(NumericValue),
+}
+
+impl EventEmitter<Event> for GraphView {}
@@ -0,0 +1,501 @@
+use crate::calculator::expression::{to_f64, Expression};
+use dashu::{integer::IBig, Decimal};
+
+const BISECTIONS: usize = 60;
+const MARKER_SIZE: f64 = 5.;
+const LINE_WIDTH: f64 = 1.5;
+const SAMPLE_PLACES: isize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mark {
+    Axis,
+    Curve(usize),
+    Root(usize),
+    Intersection(usize, usize),
+    Trace(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quad {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub mark: Mark,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    pub mark: Mark,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plot {
+    viewport: Viewport,
+    width: usize,
+    height: f64,
+    samples: Vec<Vec<Option<f64>>>,
+    points: Vec<Point>,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Viewport {
+            x_min: -10.,
+            x_max: 10.,
+            y_min: -10.,
+            y_max: 10.,
+        }
+    }
+}
+
+impl Viewport {
+    pub fn pan(&mut self, x: f64, y: f64) {
+        let dx = (self.x_max - self.x_min) * x;
+        let dy = (self.y_max - self.y_min) * y;
+
+        self.x_min += dx;
+        self.x_max += dx;
+        self.y_min += dy;
+        self.y_max += dy;
+    }
+
+    pub fn zoom(&mut self, factor: f64, x: f64, y: f64) {
+        self.x_min = x + (self.x_min - x) * factor;
+        self.x_max = x + (self.x_max - x) * factor;
+        self.y_min = y + (self.y_min - y) * factor;
+        self.y_max = y + (self.y_max - y) * factor;
+    }
+
+    pub fn center(&self) -> (f64, f64) {
+        (
+            (self.x_min + self.x_max) / 2.,
+            (self.y_min + self.y_max) / 2.,
+        )
+    }
+
+    pub fn world_x(&self, column: f64, width: f64) -> f64 {
+        self.x_min + (self.x_max - self.x_min) * column / width
+    }
+
+    pub fn world_y(&self, row: f64, height: f64) -> f64 {
+        self.y_max - (self.y_max - self.y_min) * row / height
+    }
+
+    fn screen_x(&self, x: f64, width: f64) -> f64 {
+        (x - self.x_min) / (self.x_max - self.x_min) * width
+    }
+
+    fn screen_y(&self, y: f64, height: f64) -> f64 {
+        (self.y_max - y) / (self.y_max - self.y_min) * height
+    }
+}
+
+impl Plot {
+    pub fn new(functions: &[Expression], viewport: Viewport, width: usize, height: f64) -> Self {
+        let samples: Vec<Vec<Option<f64>>> = functions
+            .iter()
+            .map(|function| {
+                (0..=width)
+                    .map(|column| value_at(function, viewport.world_x(column as f64, width as f64)))
+                    .collect()
+            })
+            .collect();
+
+        let mut points = vec![];
+
+        for (index, function) in functions.iter().enumerate() {
+            let roots = crossings(&samples[index], &vec![Some(0.); width + 1]);
+
+            points.extend(
+                refine(&roots, &viewport, width, |x| value_at(function, x))
+                    .into_iter()
+                    .map(|x| Point {
+                        x,
+                        y: 0.,
+                        mark: Mark::Root(index),
+                    }),
+            );
+        }
+
+        for (first, a) in functions.iter().enumerate() {
+            for (second, b) in functions.iter().enumerate().skip(first + 1) {
+                let crossings = crossings(&samples[first], &samples[second]);
+
+                points.extend(
+                    refine(&crossings, &viewport, width, |x| {
+                        Some(value_at(a, x)? - value_at(b, x)?)
+                    })
+                    .into_iter()
+                    .filter_map(|x| {
+                        Some(Point {
+                            x,
+                            y: value_at(a, x)?,
+                            mark: Mark::Intersection(first, second),
+                        })
+                    }),
+                );
+            }
+        }
+
+        Plot {
+            viewport,
+            width,
+            height,
+            samples,
+            points,
+        }
+    }
+
+    pub fn is_current(&self, viewport: &Viewport, width: usize, height: f64) -> bool {
+        self.viewport == *viewport && self.width == width && self.height == height
+    }
+
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    pub fn scene(&self, trace: Option<usize>) -> Vec<Quad> {
+        let width = self.width as f64;
+        let viewport = &self.viewport;
+        let mut quads = vec![];
+
+        let axis_y = viewport.screen_y(0., self.height);
+        if (0. ..=self.height).contains(&axis_y) {
+            quads.push(Quad {
+                x: 0.,
+                y: axis_y,
+                width,
+                height: 1.,
+                mark: Mark::Axis,
+            });
+        }
+
+        let axis_x = viewport.screen_x(0., width);
+        if (0. ..=width).contains(&axis_x) {
+            quads.push(Quad {
+                x: axis_x,
+                y: 0.,
+                width: 1.,
+                height: self.height,
+                mark: Mark::Axis,
+            });
+        }
+
+        for (index, samples) in self.samples.iter().enumerate() {
+            for column in 0..samples.len() {
+                let Some(y) = samples[column] else {
+                    continue;
+                };
+
+                let y = viewport.screen_y(y, self.height);
+                let previous = column
+                    .checked_sub(1)
+                    .and_then(|previous| samples[previous])
+                    .map(|previous| viewport.screen_y(previous, self.height))
+                    .filter(|previous| (previous - y).abs() < self.height);
+
+                let (top, bottom) = match previous {
+                    Some(previous) => (y.min(previous), y.max(previous)),
+                    None => (y, y),
+                };
+
+                if bottom < 0. || top > self.height {
+                    continue;
+                }
+
+                let top = top.max(0.);
+                let bottom = bottom.min(self.height);
+
+                quads.push(Quad {
+                    x: column as f64 - LINE_WIDTH / 2.,
+                    y: top - LINE_WIDTH / 2.,
+                    width: LINE_WIDTH,
+                    height: bottom - top + LINE_WIDTH,
+                    mark: Mark::Curve(index),
+                });
+            }
+        }
+
+        let markers = self
+            .points
+            .iter()
+            .map(|point| (point.x, point.y, point.mark));
+        let traced = trace
+            .into_iter()
+            .flat_map(|column| {
+                self.samples
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(index, samples)| {
+                        let y = (*samples.get(column)?)?;
+
+                        Some((
+                            viewport.world_x(column as f64, width),
+                            y,
+                            Mark::Trace(index),
+                        ))
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        for (x, y, mark) in markers.chain(traced) {
+            let x = viewport.screen_x(x, width);
+            let y = viewport.screen_y(y, self.height);
+
+            if (0. ..=width).contains(&x) && (0. ..=self.height).contains(&y) {
+                quads.push(Quad {
+                    x: x - MARKER_SIZE / 2.,
+                    y: y - MARKER_SIZE / 2.,
+                    width: MARKER_SIZE,
+                    height: MARKER_SIZE,
+                    mark,
+                });
+            }
+        }
+
+        quads
+    }
+}
+
+pub fn decimal(value: f64) -> Decimal {
+    let scale = 10f64.powi(SAMPLE_PLACES as i32);
+
+    Decimal::from_parts(IBig::from((value * scale).round() as i128), -SAMPLE_PLACES)
+}
+
+fn value_at(function: &Expression, x: f64) -> Option<f64> {
+    function
+        .evaluate(&decimal(x))
+        .map(|value| to_f64(&value))
+        .filter(|value| value.is_finite())
+}
+
+// Columns where the difference between two sampled curves changes sign.
+fn crossings(a: &[Option<f64>], b: &[Option<f64>]) -> Vec<usize> {
+    let differences: Vec<Option<f64>> = a.iter().zip(b).map(|(a, b)| Some((*a)? - (*b)?)).collect();
+
+    (1..differences.len())
+        .filter(
+            |column| match (differences[column - 1], differences[*column]) {
+                (Some(previous), Some(current)) => {
+                    current == 0. || (previous != 0. && previous.signum() != current.signum())
+                }
+                _ => false,
+            },
+        )
+        .collect()
+}
+
+fn refine(
+    columns: &[usize],
+    viewport: &Viewport,
+    width: usize,
+    function: impl Fn(f64) -> Option<f64>,
+) -> Vec<f64> {
+    let span = viewport.y_max - viewport.y_min;
+
+    columns
+        .iter()
+        .filter_map(|column| {
+            let mut low = viewport.world_x(*column as f64 - 1., width as f64);
+            let mut high = viewport.world_x(*column as f64, width as f64);
+            let mut low_value = function(low)?;
+
+            if function(high)? == 0. {
+                return Some(high);
+            }
+
+            for _ in 0..BISECTIONS {
+                let middle = (low + high) / 2.;
+                let value = function(middle)?;
+
+                if value == 0. {
+                    return Some(middle);
+                }
+
+                if value.signum() == low_value.signum() {
+                    low = middle;
+                    low_value = value;
+                } else {
+                    high = middle;
+                }
+            }
+
+            // A sign change across a pole (like 1/x at 0) is not a root.
+            let root = (low + high) / 2.;
+            function(root)
+                .filter(|value| value.abs() < span * 1e-6)
+                .map(|_| root)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_viewport {
+    use super::*;
+
+    #[test]
+    fn pans_by_fraction_of_span() {
+        let mut viewport = Viewport::default();
+        viewport.pan(0.25, -0.5);
+
+        assert_eq!(
+            viewport,
+            Viewport {
+                x_min: -5.,
+                x_max: 15.,
+                y_min: -20.,
+                y_max: 0.,
+            }
+        );
+    }
+
+    #[test]
+    fn zooms_around_point() {
+        let mut viewport = Viewport::default();
+        viewport.zoom(0.5, 10., 0.);
+
+        assert_eq!(
+            viewport,
+            Viewport {
+                x_min: 0.,
+                x_max: 10.,
+                y_min: -5.,
+                y_max: 5.,
+            }
+        );
+        assert_eq!(viewport.center(), (5., 0.));
+    }
+
+    #[test]
+    fn maps_screen_to_world() {
+        let viewport = Viewport::default();
+
+        assert_eq!(viewport.world_x(50., 200.), -5.);
+        assert_eq!(viewport.world_y(0., 100.), 10.);
+        assert_eq!(viewport.world_y(100., 100.), -10.);
+    }
+}
+
+#[cfg(test)]
+mod test_scene {
+    use super::*;
+
+    fn functions(texts: &[&str]) -> Vec<Expression> {
+        texts
+            .iter()
+            .map(|text| Expression::parse(text).unwrap())
+            .collect()
+    }
+
+    fn marked(quads: &[Quad], filter: impl Fn(&Mark) -> bool) -> Vec<(f64, f64)> {
+        quads
+            .iter()
+            .filter(|quad| filter(&quad.mark))
+            .map(|quad| (quad.x + quad.width / 2., quad.y + quad.height / 2.))
+            .collect()
+    }
+
+    #[test]
+    fn draws_axes_through_origin() {
+        let plot = Plot::new(&[], Viewport::default(), 200, 100.);
+
+        assert_eq!(
+            plot.scene(None),
+            vec![
+                Quad {
+                    x: 0.,
+                    y: 50.,
+                    width: 200.,
+                    height: 1.,
+                    mark: Mark::Axis,
+                },
+                Quad {
+                    x: 100.,
+                    y: 0.,
+                    width: 1.,
+                    height: 100.,
+                    mark: Mark::Axis,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn draws_a_curve_column_per_pixel() {
+        let plot = Plot::new(&functions(&["x"]), Viewport::default(), 200, 100.);
+        let curve = marked(&plot.scene(None), |mark| *mark == Mark::Curve(0));
+
+        assert_eq!(curve.len(), 201);
+        assert_eq!(curve[0], (0., 100.));
+        assert_eq!(curve[100], (100., 50.25));
+        assert!(curve[200].1 < 1.);
+    }
+
+    #[test]
+    fn skips_undefined_points_and_poles() {
+        let plot = Plot::new(&functions(&["sqrt(x)"]), Viewport::default(), 200, 100.);
+        assert_eq!(
+            marked(&plot.scene(None), |mark| *mark == Mark::Curve(0)).len(),
+            101
+        );
+
+        let plot = Plot::new(&functions(&["1/x"]), Viewport::default(), 200, 100.);
+        let scene = plot.scene(None);
+        assert!(scene
+            .iter()
+            .filter(|quad| quad.mark == Mark::Curve(0))
+            .all(|quad| quad.height < 100.));
+        assert!(marked(&scene, |mark| matches!(mark, Mark::Root(_))).is_empty());
+    }
+
+    #[test]
+    fn marks_roots() {
+        let plot = Plot::new(&functions(&["x^2 - 4"]), Viewport::default(), 200, 100.);
+        let roots: Vec<f64> = plot.points().iter().map(|point| point.x).collect();
+
+        assert_eq!(roots.len(), 2);
+        assert!((roots[0] + 2.).abs() < 1e-9);
+        assert!((roots[1] - 2.).abs() < 1e-9);
+        assert_eq!(
+            marked(&plot.scene(None), |mark| *mark == Mark::Root(0)).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn marks_intersections() {
+        let plot = Plot::new(&functions(&["x", "6 - x"]), Viewport::default(), 200, 100.);
+        let intersections: Vec<&Point> = plot
+            .points()
+            .iter()
+            .filter(|point| point.mark == Mark::Intersection(0, 1))
+            .collect();
+
+        assert_eq!(intersections.len(), 1);
+        assert!((intersections[0].x - 3.).abs() < 1e-9);
+        assert!((intersections[0].y - 3.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn traces_every_function_at_cursor() {
+        let plot = Plot::new(&functions(&["x", "2x"]), Viewport::default(), 200, 100.);
+        let scene = plot.scene(Some(125));
+
+        assert_eq!(
+            marked(&scene, |mark| matches!(mark, Mark::Trace(_))),
+            vec![(125., 37.5), (125., 25.)]
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use anyhow::{anyhow, Result};
+use gpui::{App, AssetSource, Global, SharedString};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_PACK: &str = "default";
+
+const MANIFEST: &str = "pack.json";
+const PACKS_DIR: &str = "packs";
+const DEFAULT_SIZE: f32 = 16.;
+const DEFAULT_TINT: u32 = 0xffffff;
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    name: String,
+    #[serde(default)]
+    icons: BTreeMap<String, IconDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IconDefinition {
+    path: String,
+    #[serde(default)]
+    size: Option<f32>,
+    #[serde(default)]
+    tint: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Icon {
+    pub path: SharedString,
+    pub size: f32,
+    pub tint: u32,
+}
+
+// Maps semantic icon names to SVGs in the asset source. Packs other than the
+// default live in `packs/<id>/pack.json` and fall back to the default pack for
+// any icon they do not define.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IconPack {
+    pub id: String,
+    pub name: String,
+    icons: BTreeMap<String, Icon>,
+}
+
+impl Global for IconPack {}
+
+impl IconPack {
+    pub fn parse(id: &str, json: &str) -> Result<IconPack> {
+        let manifest: Manifest = serde_json::from_str(json)?;
+        let directory = match id {
+            DEFAULT_PACK => String::new(),
+            id => format!("{}/{}/", PACKS_DIR, id),
+        };
+
+        let icons = manifest
+            .icons
+            .into_iter()
+            .map(|(name, definition)| {
+                let tint = match definition.tint {
+                    Some(tint) => parse_tint(&tint)?,
+                    None => DEFAULT_TINT,
+                };
+                let icon = Icon {
+                    path: format!("{}{}", directory, definition.path).into(),
+                    size: definition.size.unwrap_or(DEFAULT_SIZE),
+                    tint,
+                };
+
+                Ok((name, icon))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(IconPack {
+            id: id.to_string(),
+            name: manifest.name,
+            icons,
+        })
+    }
+
+    pub fn load(assets: &dyn AssetSource, id: &str) -> Result<IconPack> {
+        let default = Self::parse(DEFAULT_PACK, &read(assets, MANIFEST)?)?;
+        if id == DEFAULT_PACK {
+            return Ok(default);
+        }
+
+        let mut pack = Self::parse(
+            id,
+            &read(assets, &format!("{}/{}/{}", PACKS_DIR, id, MANIFEST))?,
+        )?;
+        for (name, icon) in default.icons {
+            pack.icons.entry(name).or_insert(icon);
+        }
+
+        Ok(pack)
+    }
+
+    pub fn available(assets: &dyn AssetSource) -> Result<Vec<String>> {
+        let mut packs = vec![DEFAULT_PACK.to_string()];
+
+        packs.extend(assets.list(PACKS_DIR)?.iter().filter_map(|path| {
+            let id = path
+                .strip_prefix(&format!("{}/", PACKS_DIR))?
+                .strip_suffix(&format!("/{}", MANIFEST))?;
+
+            (!id.contains('/')).then(|| id.to_string())
+        }));
+
+        Ok(packs)
+    }
+
+    pub fn icon(&self, name: &str) -> Option<&Icon> {
+        self.icons.get(name)
+    }
+}
+
+pub fn init(cx: &mut App) {
+    let pack =
+        IconPack::load(cx.asset_source().as_ref(), DEFAULT_PACK).unwrap_or_else(|_| IconPack {
+            id: DEFAULT_PACK.to_string(),
+            name: "Default".to_string(),
+            icons: BTreeMap::new(),
+        });
+
+    cx.set_global(pack);
+}
+
+pub fn next_pack(cx: &mut App) -> Result<()> {
+    let packs = IconPack::available(cx.asset_source().as_ref())?;
+    let current = cx.global::<IconPack>().id.clone();
+    let index = packs.iter().position(|id| *id == current).unwrap_or(0);
+    let next = &packs[(index + 1) % packs.len()];
+
+    let pack = IconPack::load(cx.asset_source().as_ref(), next)?;
+    cx.set_global(pack);
+    cx.refresh_windows();
+
+    Ok(())
+}
+
+fn read(assets: &dyn AssetSource, path: &str) -> Result<String> {
+    let data = assets
+        .load(path)?
+        .ok_or_else(|| anyhow!("Missing icon pack manifest {}", path))?;
+
+    Ok(String::from_utf8(data.into_owned())?)
+}
+
+fn parse_tint(tint: &str) -> Result<u32> {
+    let hex = tint.trim_start_matches('#');
+    if hex.len() != 6 {
+        return Err(anyhow!("Invalid tint {}", tint));
+    }
+
+    u32::from_str_radix(hex, 16).map_err(|_| anyhow!("Invalid tint {}", tint))
+}
+
+#[cfg(test)]
+mod test_icon_pack {
+    use super::*;
+    use crate::assets::Assets;
+    use std::{fs, path::PathBuf};
+
+    fn override_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("hello-gpui-test-packs-{}", name));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(path.join("packs").join("bold")).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn parses_manifest_with_defaults() {
+        let pack = IconPack::parse(
+            "bold",
+            r##"{ "name": "Bold", "icons": {
+                "pi": { "path": "pi.svg", "size": 20, "tint": "#ff9600" },
+                "sqrt": { "path": "root.svg" }
+            } }"##,
+        )
+        .unwrap();
+
+        assert_eq!(pack.name, "Bold");
+        assert_eq!(
+            pack.icon("pi"),
+            Some(&Icon {
+                path: "packs/bold/pi.svg".into(),
+                size: 20.,
+                tint: 0xff9600,
+            })
+        );
+        assert_eq!(pack.icon("sqrt").unwrap().size, DEFAULT_SIZE);
+        assert_eq!(pack.icon("sqrt").unwrap().tint, DEFAULT_TINT);
+        assert!(pack.icon("missing").is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_tints() {
+        let json = r#"{ "name": "Bad", "icons": { "pi": { "path": "pi.svg", "tint": "red" } } }"#;
+
+        assert!(IconPack::parse("bad", json).is_err());
+    }
+
+    #[test]
+    fn loads_embedded_default_pack() {
+        let assets = Assets::new(None);
+        let pack = IconPack::load(&assets, DEFAULT_PACK).unwrap();
+
+        for name in ["mode", "sqrt", "undo"] {
+            let icon = pack.icon(name).unwrap();
+            assert!(assets.load(&icon.path).unwrap().is_some(), "{}", name);
+        }
+        assert_eq!(IconPack::available(&assets).unwrap(), vec![DEFAULT_PACK]);
+    }
+
+    #[test]
+    fn loads_override_pack_over_default() {
+        let base = override_dir("load");
+        fs::write(
+            base.join("packs").join("bold").join("pack.json"),
+            r#"{ "name": "Bold", "icons": { "undo": { "path": "undo.svg", "size": 12 } } }"#,
+        )
+        .unwrap();
+        let assets = Assets::new(Some(base));
+
+        assert_eq!(
+            IconPack::available(&assets).unwrap(),
+            vec![DEFAULT_PACK, "bold"]
+        );
+
+        let pack = IconPack::load(&assets, "bold").unwrap();
+        assert_eq!(pack.icon("undo").unwrap().path, "packs/bold/undo.svg");
+        assert_eq!(pack.icon("undo").unwrap().size, 12.);
+        assert_eq!(pack.icon("mode").unwrap().path, "rocket.svg");
+        assert!(IconPack::load(&assets, "missing").is_err());
+    }
+}
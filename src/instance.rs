@@ -0,0 +1,268 @@
+use anyhow::{anyhow, Result};
+use std::{
+    fs,
+    io::{BufRead, BufReader, ErrorKind, Write},
+    os::unix::net::{UnixListener, UnixStream},
+    path::Path,
+    time::Duration,
+};
+
+const USAGE: &str = "Usage: hello-gpui [--rpc] [--show | -e <expression>]";
+// How long the running instance waits for a client to send its command or
+// read the reply, so an idle client can't stall the ones behind it.
+const SERVER_TIMEOUT: Duration = Duration::from_secs(1);
+// How long a second launch waits for the running instance to answer.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Command {
+    Show,
+    Evaluate(String),
+}
+
+pub type Reply = Result<String, String>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub command: Command,
+    // Serve JSON-RPC on its own socket, see `rpc`.
+    pub rpc: bool,
+}
+
+// A command read from a second launch, answered once it has been handled.
+pub struct Request {
+    pub command: Command,
+    stream: UnixStream,
+}
+
+impl Command {
+    pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command> {
+        let args: Vec<String> = args.into_iter().collect();
+
+        match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            [] | ["--show"] => Ok(Command::Show),
+            ["-e" | "--eval", expression] => Ok(Command::Evaluate(expression.to_string())),
+            _ => Err(anyhow!(USAGE)),
+        }
+    }
+}
+
+impl Options {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Options> {
+        let (flags, args): (Vec<String>, Vec<String>) =
+            args.into_iter().partition(|arg| arg == "--rpc");
+
+        Ok(Options {
+            command: Command::parse_args(args)?,
+            rpc: !flags.is_empty(),
+        })
+    }
+}
+
+impl Request {
+    pub fn reply(mut self, reply: Reply) -> Result<()> {
+        writeln!(self.stream, "{}", serde_json::to_string(&reply)?)?;
+
+        Ok(())
+    }
+}
+
+// Forwards the command to a running instance and returns its reply, or
+// returns None when no instance is listening.
+pub fn send(path: &Path, command: &Command) -> Result<Option<Reply>> {
+    let mut stream = match UnixStream::connect(path) {
+        Ok(stream) => stream,
+        Err(err)
+            if matches!(
+                err.kind(),
+                ErrorKind::NotFound | ErrorKind::ConnectionRefused
+            ) =>
+        {
+            return Ok(None)
+        }
+        Err(err) => return Err(err.into()),
+    };
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+    writeln!(stream, "{}", serde_json::to_string(command)?)?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+
+    Ok(Some(serde_json::from_str(&line)?))
+}
+
+// Binds the socket, replacing one left behind by an instance that exited
+// without cleaning up. Call only after `send` found no running instance.
+pub fn listen(path: &Path) -> Result<UnixListener> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != ErrorKind::NotFound => return Err(err.into()),
+        _ => {}
+    }
+
+    Ok(UnixListener::bind(path)?)
+}
+
+// Blocks until a well-formed request arrives. Malformed ones are answered
+// with an error and skipped, as are clients that send nothing in time.
+pub fn accept(listener: &UnixListener) -> Result<Request> {
+    loop {
+        let (stream, _) = listener.accept()?;
+        if stream.set_read_timeout(Some(SERVER_TIMEOUT)).is_err()
+            || stream.set_write_timeout(Some(SERVER_TIMEOUT)).is_err()
+        {
+            continue;
+        }
+
+        let mut line = String::new();
+        if BufReader::new(&stream).read_line(&mut line).is_err() {
+            continue;
+        }
+
+        let request = Request {
+            command: Command::Show,
+            stream,
+        };
+
+        match serde_json::from_str(&line) {
+            Ok(command) => return Ok(Request { command, ..request }),
+            Err(err) => {
+                request.reply(Err(err.to_string())).ok();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_instance {
+    use super::*;
+    use std::{path::PathBuf, thread};
+
+    fn socket(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("hello-gpui-test-{}.sock", name))
+    }
+
+    fn args(args: &[&str]) -> Result<Command> {
+        Command::parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_arguments() {
+        assert_eq!(args(&[]).unwrap(), Command::Show);
+        assert_eq!(args(&["--show"]).unwrap(), Command::Show);
+        assert_eq!(
+            args(&["-e", "2 + 3"]).unwrap(),
+            Command::Evaluate("2 + 3".into())
+        );
+        assert_eq!(
+            args(&["--eval", "x * 2"]).unwrap(),
+            Command::Evaluate("x * 2".into())
+        );
+        assert!(args(&["-e"]).is_err());
+        assert!(args(&["--show", "-e", "1"]).is_err());
+    }
+
+    #[test]
+    fn parses_rpc_flag() {
+        let options = |args: &[&str]| Options::parse(args.iter().map(|arg| arg.to_string()));
+
+        assert_eq!(
+            options(&["--rpc", "-e", "1"]).unwrap(),
+            Options {
+                command: Command::Evaluate("1".into()),
+                rpc: true,
+            }
+        );
+        assert!(!options(&["--show"]).unwrap().rpc);
+        assert!(options(&["-e", "--rpc"]).is_err());
+    }
+
+    #[test]
+    fn finds_no_running_instance() {
+        let path = socket("missing");
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(send(&path, &Command::Show).unwrap(), None);
+
+        drop(listen(&path).unwrap());
+        assert_eq!(send(&path, &Command::Show).unwrap(), None);
+    }
+
+    #[test]
+    fn forwards_commands_and_replies() {
+        let path = socket("forward");
+        let listener = listen(&path).unwrap();
+
+        let server = thread::spawn(move || {
+            for _ in 0..2 {
+                let request = accept(&listener).unwrap();
+                let reply = match &request.command {
+                    Command::Evaluate(expression) => Ok(format!("{} = 5", expression)),
+                    Command::Show => Err("hidden".to_string()),
+                };
+                request.reply(reply).unwrap();
+            }
+        });
+
+        assert_eq!(
+            send(&path, &Command::Evaluate("2 + 3".into())).unwrap(),
+            Some(Ok("2 + 3 = 5".into()))
+        );
+        assert_eq!(
+            send(&path, &Command::Show).unwrap(),
+            Some(Err("hidden".into()))
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn answers_malformed_requests() {
+        let path = socket("malformed");
+        let listener = listen(&path).unwrap();
+
+        let server = thread::spawn(move || accept(&listener).unwrap().command);
+
+        let mut stream = UnixStream::connect(&path).unwrap();
+        writeln!(stream, "not json").unwrap();
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line).unwrap();
+        assert!(serde_json::from_str::<Reply>(&line).unwrap().is_err());
+
+        let mut stream = UnixStream::connect(&path).unwrap();
+        writeln!(stream, "{}", serde_json::to_string(&Command::Show).unwrap()).unwrap();
+        assert_eq!(server.join().unwrap(), Command::Show);
+    }
+
+    #[test]
+    fn skips_idle_clients() {
+        let path = socket("idle");
+        let listener = listen(&path).unwrap();
+
+        let server = thread::spawn(move || accept(&listener).unwrap().command);
+
+        let _idle = UnixStream::connect(&path).unwrap();
+        let mut stream = UnixStream::connect(&path).unwrap();
+        writeln!(stream, "{}", serde_json::to_string(&Command::Show).unwrap()).unwrap();
+        assert_eq!(server.join().unwrap(), Command::Show);
+    }
+
+    #[test]
+    fn times_out_waiting_for_a_reply() {
+        let path = socket("silent");
+        let listener = listen(&path).unwrap();
+
+        // Reads until the client gives up and hangs up, never replying.
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            std::io::copy(&mut stream, &mut std::io::sink()).unwrap();
+        });
+
+        assert!(send(&path, &Command::Show).is_err());
+        server.join().unwrap();
+    }
+}
@@ -1,9 +1,8 @@
-use gpui::{
-    point, prelude::*, px, size, App, Application, Bounds, DisplayId, Pixels, Point, Size,
-    TitlebarOptions, WindowBounds, WindowOptions,
-};
-use std::path::PathBuf;
+use gpui::{App, Application};
+use instance::{Command, Options};
+use std::{env, process};
 
+#[cfg(test)]
 #[macro_use]
 extern crate dashu_macros;
 
@@ -13,77 +12,91 @@ extern crate serde_derive;
 #[macro_use]
 extern crate schemars;
 
+mod action_button;
 mod assets;
 mod calculator;
+mod converter;
+mod currency;
+mod dates;
+mod definitions;
+mod finance;
+mod graph;
+mod icon_pack;
+mod instance;
+mod paths;
 mod round_button;
+mod rpc;
+mod settings;
+mod solver;
+mod statistics;
+mod tabs;
+mod tape;
+mod window_state;
+mod workspace;
 
 fn main() {
-    println!("Starting calculator");
-    Application::new()
-        .with_assets(assets::Assets::new(
-            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("icons"),
-        ))
-        .run(|cx: &mut App| {
-            #[cfg(not(debug_assertions))]
-            let bounds = Bounds::centered(None, size(px(198.), px(350.0)), cx);
+    let Options { command, rpc } = Options::parse(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2);
+    });
 
-            #[cfg(debug_assertions)]
-            let bounds = bounds_top_right(
-                cx,
-                None,
-                size(px(198.), px(350.0)),
-                Point {
-                    x: px(25.),
-                    y: px(50.),
-                },
-            );
+    // A running instance handles the command and this launch exits.
+    let socket = paths::socket_file();
+    match instance::send(&socket, &command) {
+        Ok(Some(Ok(reply))) => {
+            if rpc {
+                eprintln!("--rpc is ignored while a calculator is already running");
+            }
+            if !reply.is_empty() {
+                println!("{}", reply);
+            }
+            return;
+        }
+        Ok(Some(Err(err))) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+        Ok(None) => {}
+        // Another instance owns the socket, so taking it over would orphan it.
+        Err(err) => {
+            eprintln!("Failed to reach the running calculator: {}", err);
+            process::exit(1);
+        }
+    }
 
+    let listener = instance::listen(&socket)
+        .inspect_err(|err| eprintln!("Failed to listen on {:?}: {}", socket, err))
+        .ok();
+
+    let rpc_socket = paths::rpc_socket_file();
+    let rpc_listener = rpc
+        .then(|| {
+            instance::listen(&rpc_socket)
+                .inspect_err(|err| eprintln!("Failed to listen on {:?}: {}", rpc_socket, err))
+                .ok()
+        })
+        .flatten();
+
+    println!("Starting calculator");
+    Application::new()
+        .with_assets(assets::Assets::new(Some(paths::icons_dir())))
+        .run(move |cx: &mut App| {
             cx.activate(true);
+            icon_pack::init(cx);
             calculator::component::init(cx);
+            workspace::init(cx);
 
-            let window_options = WindowOptions {
-                window_bounds: Some(WindowBounds::Windowed(bounds)),
-                window_min_size: Some(size(px(198.), px(350.))),
-                window_background: gpui::WindowBackgroundAppearance::Blurred,
-                titlebar: Some(TitlebarOptions {
-                    title: None,
-                    traffic_light_position: None,
-                    appears_transparent: true,
-                }),
-                app_id: Some("com.dev.hello-gpui".to_string()),
-                kind: gpui::WindowKind::Normal,
-                ..Default::default()
-            };
-
-            cx.open_window(window_options, |window, cx| {
-                cx.new(|c| calculator::component::Calculator::new(window, c))
-            })
-            .unwrap();
+            workspace::open_window(cx);
+            if let Some(listener) = listener {
+                workspace::serve(listener, cx);
+            }
+            if let Some(listener) = rpc_listener {
+                rpc::server::serve(listener, cx);
+            }
+            if let Command::Evaluate(_) = command {
+                if let Err(err) = workspace::run(&command, cx) {
+                    eprintln!("{}", err);
+                }
+            }
         });
 }
-
-fn bounds_top_right(
-    cx: &mut App,
-    display_id: Option<DisplayId>,
-    size: Size<Pixels>,
-    offset: Point<Pixels>,
-) -> Bounds<Pixels> {
-    let display = display_id
-        .and_then(|id| cx.find_display(id))
-        .or_else(|| cx.primary_display());
-
-    display
-        .map(|display| {
-            let origin = display.bounds().top_right();
-            let origin: Point<Pixels> = Point {
-                x: origin.x - offset.x,
-                y: origin.y + offset.y,
-            };
-
-            Bounds::from_corner_and_size(gpui::Corner::TopRight, origin, size)
-        })
-        .unwrap_or_else(|| Bounds {
-            origin: point(px(0.), px(0.)),
-            size,
-        })
-}
@@ -0,0 +1,58 @@
+use std::{env, path::PathBuf};
+
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("hello-gpui")
+}
+
+pub fn definitions_file() -> PathBuf {
+    config_dir().join("definitions.json")
+}
+
+pub fn icons_dir() -> PathBuf {
+    env::var("HELLO_GPUI_ICONS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| config_dir().join("icons"))
+}
+
+pub fn rates_file() -> PathBuf {
+    if let Ok(path) = env::var("HELLO_GPUI_RATES") {
+        return PathBuf::from(path);
+    }
+
+    let csv = config_dir().join("rates.csv");
+    if csv.exists() {
+        return csv;
+    }
+
+    config_dir().join("rates.json")
+}
+
+pub fn window_state_file() -> PathBuf {
+    config_dir().join("window.json")
+}
+
+pub fn settings_file() -> PathBuf {
+    config_dir().join("settings.json")
+}
+
+pub fn socket_file() -> PathBuf {
+    if let Ok(path) = env::var("HELLO_GPUI_SOCKET") {
+        return PathBuf::from(path);
+    }
+
+    dirs::runtime_dir()
+        .unwrap_or_else(env::temp_dir)
+        .join("hello-gpui.sock")
+}
+
+pub fn rpc_socket_file() -> PathBuf {
+    if let Ok(path) = env::var("HELLO_GPUI_RPC_SOCKET") {
+        return PathBuf::from(path);
+    }
+
+    dirs::runtime_dir()
+        .unwrap_or_else(env::temp_dir)
+        .join("hello-gpui-rpc.sock")
+}
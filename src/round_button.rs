@@ -1,4 +1,6 @@
-use gpui::{div, prelude::*, px, rgb, rgba, AnyElement, App, ClickEvent, ElementId, Rgba, Window};
+use gpui::{
+    div, prelude::*, px, rgb, rgba, AnyElement, App, ClickEvent, ElementId, Pixels, Rgba, Window,
+};
 
 pub type ClickFn = dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static;
 
@@ -8,6 +10,8 @@ pub struct RoundButton {
     pub bg: Rgba,
     active_bg: Rgba,
     is_disabled: bool,
+    is_focused: bool,
+    size: Pixels,
     on_click: Option<Box<ClickFn>>,
     label: AnyElement,
 }
@@ -24,10 +28,22 @@ impl RoundButton {
             bg,
             active_bg,
             is_disabled: false,
+            is_focused: false,
+            size: px(42.),
             on_click: None,
         }
     }
 
+    pub fn focused(mut self, is_focused: bool) -> Self {
+        self.is_focused = is_focused;
+        self
+    }
+
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = size;
+        self
+    }
+
     pub fn on_click(
         mut self,
         handler: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
@@ -42,9 +58,12 @@ impl RenderOnce for RoundButton {
         div()
             .id(self.id.clone())
             .bg(self.bg)
-            .w(px(42.))
-            .h(px(42.))
+            .w(self.size)
+            .h(self.size)
             .rounded_full()
+            .when(self.is_focused, |this| {
+                this.border_2().border_color(rgb(0xffffff))
+            })
             .when_some(self.on_click, |this, on_click| {
                 this.on_click(move |evt, win, app| (on_click)(evt, win, app))
             })
@@ -53,10 +72,10 @@ impl RenderOnce for RoundButton {
             })
             .child(
                 div()
+                    .size_full()
                     .flex()
                     .justify_center()
                     .items_center()
-                    .py(px(9.))
                     .child(self.label),
             )
     }
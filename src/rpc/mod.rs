@@ -0,0 +1,3 @@
+pub mod outbox;
+pub mod protocol;
+pub mod server;
@@ -0,0 +1,92 @@
+use std::{
+    io::Write,
+    net::Shutdown,
+    os::unix::net::UnixStream,
+    sync::mpsc::{self, SyncSender, TrySendError},
+    thread,
+};
+
+// Lines queued for a client before it is considered too slow and dropped.
+const CAPACITY: usize = 256;
+
+// Writes lines to a client on its own thread so a client that stops reading
+// never blocks the caller. Once the queue fills up the client is disconnected.
+pub struct Outbox {
+    sender: SyncSender<String>,
+    stream: UnixStream,
+}
+
+impl Outbox {
+    pub fn new(stream: UnixStream) -> std::io::Result<Outbox> {
+        let (sender, receiver) = mpsc::sync_channel::<String>(CAPACITY);
+        let mut writer = stream.try_clone()?;
+
+        thread::spawn(move || {
+            for line in receiver {
+                if writeln!(writer, "{}", line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Outbox { sender, stream })
+    }
+
+    // Returns false once the client has been dropped.
+    pub fn send(&self, line: String) -> bool {
+        match self.sender.try_send(line) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                // Unblocks the writer thread and ends the client's read loop.
+                self.stream.shutdown(Shutdown::Both).ok();
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_outbox {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read},
+        time::{Duration, Instant},
+    };
+
+    #[test]
+    fn delivers_lines_in_order() {
+        let (server, client) = UnixStream::pair().unwrap();
+        let outbox = Outbox::new(server).unwrap();
+
+        assert!(outbox.send("one".into()));
+        assert!(outbox.send("two".into()));
+
+        let mut lines = BufReader::new(client).lines();
+        assert_eq!(lines.next().unwrap().unwrap(), "one");
+        assert_eq!(lines.next().unwrap().unwrap(), "two");
+    }
+
+    #[test]
+    fn drops_a_client_that_never_reads() {
+        let (server, mut client) = UnixStream::pair().unwrap();
+        let outbox = Outbox::new(server).unwrap();
+        let line = "x".repeat(64 * 1024);
+        let started = Instant::now();
+
+        let sent = (0..10_000)
+            .take_while(|_| outbox.send(line.clone()))
+            .count();
+
+        assert!(sent < 10_000);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!outbox.send(line));
+
+        // The client sees its connection closed rather than hanging.
+        client
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut received = Vec::new();
+        client.read_to_end(&mut received).unwrap();
+    }
+}
@@ -0,0 +1,238 @@
+use crate::calculator::{action::CalculatorAction, calculation::Calculation};
+use serde_json::{json, Value};
+use std::fmt::Display;
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+// Application errors, such as no open window or an undefined result.
+pub const FAILED: i64 = -32000;
+
+const METHODS: [&str; 6] = [
+    "dispatch",
+    "state",
+    "evaluate",
+    "subscribe",
+    "unsubscribe",
+    "schema",
+];
+
+#[derive(Debug, Clone, PartialEq, Deserialize, JsonSchema)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum Method {
+    // Performs the action as if its key had been pressed and returns the state.
+    Dispatch(CalculatorAction),
+    State,
+    // Evaluates with `x` bound to the current entry and enters the result.
+    Evaluate(String),
+    // Sends a `changed` notification with the state whenever it changes.
+    Subscribe,
+    Unsubscribe,
+    Schema,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct State {
+    pub current: String,
+    pub past: String,
+    // What a screen reader would read out last, such as a result or a focused key.
+    pub announcement: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    // Requests without an id are notifications and get no response.
+    pub id: Option<Value>,
+    pub method: Method,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub code: i64,
+    pub message: String,
+}
+
+impl State {
+    pub fn new(calculation: &Calculation, announcement: &str) -> State {
+        State {
+            current: calculation.current_operation_string(),
+            past: calculation.past_operations_string(),
+            announcement: announcement.to_string(),
+        }
+    }
+}
+
+impl Error {
+    pub fn new(code: i64, message: impl Display) -> Error {
+        Error {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+pub fn parse(line: &str) -> Result<Request, (Value, Error)> {
+    let value: Value =
+        serde_json::from_str(line).map_err(|err| (Value::Null, Error::new(PARSE_ERROR, err)))?;
+    let id = value.get("id").cloned();
+    let fail =
+        |code, message: &dyn Display| (id.clone().unwrap_or_default(), Error::new(code, message));
+
+    if value.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Err(fail(INVALID_REQUEST, &"Expected jsonrpc 2.0"));
+    }
+
+    let Some(name) = value.get("method").and_then(Value::as_str) else {
+        return Err(fail(INVALID_REQUEST, &"Missing method"));
+    };
+    if !METHODS.contains(&name) {
+        return Err(fail(METHOD_NOT_FOUND, &format!("Unknown method {}", name)));
+    }
+
+    let method = serde_json::from_value(value.clone()).map_err(|err| fail(INVALID_PARAMS, &err))?;
+
+    Ok(Request { id, method })
+}
+
+pub fn response(id: Value, result: Result<Value, Error>) -> String {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    }
+    .to_string()
+}
+
+pub fn notification(state: &State) -> String {
+    json!({ "jsonrpc": "2.0", "method": "changed", "params": state }).to_string()
+}
+
+pub fn schema() -> Value {
+    json!({
+        "methods": schema_for!(Method),
+        "state": schema_for!(State),
+    })
+}
+
+#[cfg(test)]
+mod test_protocol {
+    use super::*;
+    use crate::calculator::calculation::Operation;
+
+    fn request(json: &str) -> Request {
+        parse(json).unwrap()
+    }
+
+    fn error(json: &str) -> (Value, i64) {
+        let (id, error) = parse(json).unwrap_err();
+        (id, error.code)
+    }
+
+    #[test]
+    fn parses_methods() {
+        assert_eq!(
+            request(r#"{"jsonrpc":"2.0","id":1,"method":"dispatch","params":{"Numeric":7}}"#),
+            Request {
+                id: Some(json!(1)),
+                method: Method::Dispatch(CalculatorAction::Numeric(7)),
+            }
+        );
+        assert_eq!(
+            request(r#"{"jsonrpc":"2.0","id":"a","method":"dispatch","params":"Calculate"}"#)
+                .method,
+            Method::Dispatch(CalculatorAction::Calculate)
+        );
+        assert_eq!(
+            request(r#"{"jsonrpc":"2.0","id":2,"method":"evaluate","params":"x * 2"}"#).method,
+            Method::Evaluate("x * 2".into())
+        );
+        assert_eq!(
+            request(r#"{"jsonrpc":"2.0","method":"subscribe"}"#),
+            Request {
+                id: None,
+                method: Method::Subscribe,
+            }
+        );
+        assert_eq!(
+            request(r#"{"jsonrpc":"2.0","id":3,"method":"state","params":null}"#).method,
+            Method::State
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(error("{"), (Value::Null, PARSE_ERROR));
+        assert_eq!(
+            error(r#"{"id":1,"method":"state"}"#),
+            (json!(1), INVALID_REQUEST)
+        );
+        assert_eq!(
+            error(r#"{"jsonrpc":"2.0","id":1,"method":"launch"}"#),
+            (json!(1), METHOD_NOT_FOUND)
+        );
+        assert_eq!(
+            error(r#"{"jsonrpc":"2.0","id":1,"method":"dispatch","params":{"Numeric":"x"}}"#),
+            (json!(1), INVALID_PARAMS)
+        );
+    }
+
+    #[test]
+    fn formats_responses() {
+        let state = State {
+            current: "2 + 3".into(),
+            past: "".into(),
+            announcement: "".into(),
+        };
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&response(json!(1), Ok(json!(state)))).unwrap(),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "current": "2 + 3", "past": "", "announcement": "" },
+            })
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&response(Value::Null, Err(Error::new(FAILED, "no"))))
+                .unwrap(),
+            json!({ "jsonrpc": "2.0", "id": null, "error": { "code": FAILED, "message": "no" } })
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&notification(&state)).unwrap()["method"],
+            json!("changed")
+        );
+    }
+
+    #[test]
+    fn reads_state_from_calculation() {
+        let mut calculation = Calculation::default();
+        calculation.append_number(2);
+        calculation.append_operation(Operation::Addition);
+        calculation.append_number(3);
+        calculation.calculate();
+
+        assert_eq!(
+            State::new(&calculation, "2 plus 3 equals 5"),
+            State {
+                current: "5".into(),
+                past: "2+3".into(),
+                announcement: "2 plus 3 equals 5".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn describes_methods_and_state() {
+        let schema = schema().to_string();
+
+        for method in METHODS {
+            assert!(schema.contains(&format!("\"{}\"", method)), "{}", method);
+        }
+        assert!(schema.contains("CalculatorAction"));
+        assert!(schema.contains("\"current\""));
+    }
+}
@@ -0,0 +1,141 @@
+use super::{
+    outbox::Outbox,
+    protocol::{self, Error, Method, State, FAILED},
+};
+use crate::{
+    calculator::{component::Calculator, expression::Expression},
+    workspace,
+};
+use gpui::{App, AsyncApp, Entity, Subscription};
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, BufReader},
+    os::unix::net::{UnixListener, UnixStream},
+    rc::Rc,
+    sync::Arc,
+};
+
+// Serves each client on its own task until the app quits. Requests act on
+// the active tab of the frontmost calculator window.
+pub fn serve(listener: UnixListener, cx: &mut App) {
+    let listener = Arc::new(listener);
+
+    cx.spawn(|cx| async move {
+        loop {
+            let listener = listener.clone();
+            let Ok((stream, _)) = cx
+                .background_executor()
+                .spawn(async move { listener.accept() })
+                .await
+            else {
+                break;
+            };
+
+            cx.spawn(|cx| connection(stream, cx)).detach();
+        }
+    })
+    .detach();
+}
+
+async fn connection(stream: UnixStream, cx: AsyncApp) -> Option<()> {
+    // Writes never block the UI thread; a client that stops reading is dropped.
+    let outbox = Rc::new(Outbox::new(stream.try_clone().ok()?).ok()?);
+    let mut reader = BufReader::new(stream);
+    // Dropping the subscription when the client disconnects stops notifications.
+    let mut subscription = None;
+
+    loop {
+        let (read, line, returned) = cx
+            .background_executor()
+            .spawn(async move {
+                let mut line = String::new();
+                let read = reader.read_line(&mut line);
+                (read, line, reader)
+            })
+            .await;
+        reader = returned;
+
+        if !matches!(read, Ok(1..)) {
+            return Some(());
+        }
+
+        let response = cx
+            .update(|cx| handle(&line, &outbox, &mut subscription, cx))
+            .ok()?;
+        if let Some(response) = response {
+            if !outbox.send(response) {
+                return Some(());
+            }
+        }
+    }
+}
+
+fn handle(
+    line: &str,
+    outbox: &Rc<Outbox>,
+    subscription: &mut Option<Subscription>,
+    cx: &mut App,
+) -> Option<String> {
+    let request = match protocol::parse(line) {
+        Ok(request) => request,
+        Err((id, error)) => return Some(protocol::response(id, Err(error))),
+    };
+
+    let result = match request.method {
+        Method::Dispatch(action) => calculator(cx).map(|calculator| {
+            calculator.update(cx, |calculator, cx| calculator.perform(&action, cx));
+            state(&calculator, cx)
+        }),
+        Method::State => calculator(cx).map(|calculator| state(&calculator, cx)),
+        Method::Evaluate(text) => evaluate(&text, cx),
+        Method::Subscribe => calculator(cx).map(|calculator| {
+            *subscription = Some(subscribe(calculator, outbox.clone(), cx));
+            json!(true)
+        }),
+        Method::Unsubscribe => {
+            *subscription = None;
+            Ok(json!(true))
+        }
+        Method::Schema => Ok(protocol::schema()),
+    };
+
+    request.id.map(|id| protocol::response(id, result))
+}
+
+fn calculator(cx: &mut App) -> Result<Entity<Calculator>, Error> {
+    workspace::active_calculator(cx).ok_or_else(|| Error::new(FAILED, "No calculator is open"))
+}
+
+fn state(calculator: &Entity<Calculator>, cx: &App) -> Value {
+    json!(read_state(calculator, cx))
+}
+
+fn read_state(calculator: &Entity<Calculator>, cx: &App) -> State {
+    let calculator = calculator.read(cx);
+
+    State::new(calculator.calculation(), calculator.announcement())
+}
+
+fn evaluate(text: &str, cx: &mut App) -> Result<Value, Error> {
+    let expression = Expression::parse(text).map_err(|err| Error::new(FAILED, err))?;
+
+    calculator(cx)?
+        .update(cx, |calculator, cx| calculator.evaluate(&expression, cx))
+        .map(Value::String)
+        .ok_or_else(|| Error::new(FAILED, format!("{} is undefined", text)))
+}
+
+// Calculators also notify for caret changes, so only changes to the operation
+// strings or the announcement are sent.
+fn subscribe(calculator: Entity<Calculator>, outbox: Rc<Outbox>, cx: &mut App) -> Subscription {
+    let mut last = read_state(&calculator, cx);
+
+    cx.observe(&calculator, move |calculator, cx| {
+        let state = read_state(&calculator, cx);
+
+        if state != last {
+            outbox.send(protocol::notification(&state));
+            last = state;
+        }
+    })
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use std::{fs, path::Path};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Pane {
+    Scientific,
+    Tape,
+}
+
+// From `min_width` on, the panes are shown next to the keypad.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Breakpoint {
+    pub min_width: f32,
+    pub panes: Vec<Pane>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutSettings {
+    pub min_button: f32,
+    pub max_button: f32,
+    pub breakpoints: Vec<Breakpoint>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub layout: LayoutSettings,
+}
+
+impl Default for LayoutSettings {
+    fn default() -> Self {
+        LayoutSettings {
+            min_button: 32.,
+            max_button: 72.,
+            breakpoints: vec![
+                Breakpoint {
+                    min_width: 320.,
+                    panes: vec![Pane::Scientific],
+                },
+                Breakpoint {
+                    min_width: 480.,
+                    panes: vec![Pane::Scientific, Pane::Tape],
+                },
+            ],
+        }
+    }
+}
+
+impl Settings {
+    pub fn parse(json: &str) -> Result<Settings> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Settings> {
+        if !path.exists() {
+            return Ok(Settings::default());
+        }
+
+        Self::parse(&fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod test_settings {
+    use super::*;
+
+    #[test]
+    fn fills_in_missing_fields() {
+        let settings = Settings::parse(r#"{ "layout": { "max_button": 56 } }"#).unwrap();
+
+        assert_eq!(settings.layout.max_button, 56.);
+        assert_eq!(settings.layout.min_button, 32.);
+        assert_eq!(settings.layout.breakpoints.len(), 2);
+        assert_eq!(Settings::parse("{}").unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn parses_breakpoints() {
+        let settings = Settings::parse(
+            r#"{ "layout": { "breakpoints": [{ "min_width": 400, "panes": ["Tape"] }] } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            settings.layout.breakpoints,
+            vec![Breakpoint {
+                min_width: 400.,
+                panes: vec![Pane::Tape],
+            }]
+        );
+        assert!(Settings::parse(r#"{ "layout": { "breakpoints": [{}] } }"#).is_err());
+    }
+}
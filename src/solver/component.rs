@@ -0,0 +1,283 @@
+use super::{linear, polynomial};
+use crate::{
+    action_button::action_button,
+    calculator::{
+        calculation::{into_operand, OperandValue},
+        numeric_value::NumericValue,
+    },
+};
+use dashu::Decimal;
+use gpui::{div, prelude::*, px, rgb, Context, ElementId, EventEmitter, SharedString, Window};
+
+const MAX_DEGREE: usize = 4;
+const MAX_SIZE: usize = 4;
+const POWERS: [&str; MAX_DEGREE + 1] = ["x⁴", "x³", "x²", "x", "1"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SolverKind {
+    Polynomial,
+    Linear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cell {
+    Coefficient(usize),
+    Matrix(usize, usize),
+    Constant(usize),
+}
+
+pub struct SolverView {
+    kind: SolverKind,
+    input: NumericValue,
+    degree: usize,
+    coefficients: Vec<Decimal>,
+    size: usize,
+    matrix: Vec<Vec<Decimal>>,
+    constants: Vec<Decimal>,
+    results: Vec<OperandValue>,
+    error: Option<SharedString>,
+}
+
+impl SolverView {
+    pub fn new() -> Self {
+        SolverView {
+            kind: SolverKind::Polynomial,
+            input: NumericValue::default(),
+            degree: 2,
+            coefficients: vec![Decimal::ZERO; MAX_DEGREE + 1],
+            size: 2,
+            matrix: vec![vec![Decimal::ZERO; MAX_SIZE]; MAX_SIZE],
+            constants: vec![Decimal::ZERO; MAX_SIZE],
+            results: vec![],
+            error: None,
+        }
+    }
+
+    pub fn set_input(&mut self, input: NumericValue) {
+        self.input = input;
+    }
+
+    fn reset_results(&mut self, cx: &mut Context<Self>) {
+        self.results = vec![];
+        self.error = None;
+        cx.notify();
+    }
+
+    fn toggle_kind(&mut self, cx: &mut Context<Self>) {
+        self.kind = match self.kind {
+            SolverKind::Polynomial => SolverKind::Linear,
+            SolverKind::Linear => SolverKind::Polynomial,
+        };
+        self.reset_results(cx);
+    }
+
+    fn next_size(&mut self, cx: &mut Context<Self>) {
+        match self.kind {
+            SolverKind::Polynomial => self.degree = self.degree % MAX_DEGREE + 1,
+            SolverKind::Linear => self.size = self.size % MAX_SIZE + 1,
+        }
+        self.reset_results(cx);
+    }
+
+    fn value(&self, cell: Cell) -> &Decimal {
+        match cell {
+            Cell::Coefficient(index) => &self.coefficients[index],
+            Cell::Matrix(row, column) => &self.matrix[row][column],
+            Cell::Constant(row) => &self.constants[row],
+        }
+    }
+
+    fn store(&mut self, cell: Cell, cx: &mut Context<Self>) {
+        let value = self.input.val().clone();
+
+        match cell {
+            Cell::Coefficient(index) => self.coefficients[index] = value,
+            Cell::Matrix(row, column) => self.matrix[row][column] = value,
+            Cell::Constant(row) => self.constants[row] = value,
+        }
+        self.reset_results(cx);
+    }
+
+    fn solve(&mut self, cx: &mut Context<Self>) {
+        let results = match self.kind {
+            SolverKind::Polynomial => {
+                polynomial::roots(&self.coefficients[MAX_DEGREE - self.degree..])
+                    .map(|roots| roots.into_iter().map(into_operand).collect())
+            }
+            SolverKind::Linear => {
+                let matrix: Vec<Vec<Decimal>> = self.matrix[..self.size]
+                    .iter()
+                    .map(|row| row[..self.size].to_vec())
+                    .collect();
+
+                linear::solve(&matrix, &self.constants[..self.size]).map(|solution| {
+                    solution
+                        .into_iter()
+                        .map(|value| OperandValue::Number(NumericValue::new(value)))
+                        .collect()
+                })
+            }
+        };
+
+        match results {
+            Ok(results) => {
+                self.results = results;
+                self.error = None;
+            }
+            Err(err) => {
+                self.results = vec![];
+                self.error = Some(err.to_string().into());
+            }
+        }
+
+        cx.notify();
+    }
+
+    fn render_cell(
+        &self,
+        id: impl Into<ElementId>,
+        cell: Cell,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(id)
+            .flex_1()
+            .min_w(px(20.))
+            .px(px(3.))
+            .rounded_sm()
+            .bg(rgb(0x3a3a3a))
+            .overflow_hidden()
+            .text_color(rgb(0xffffff))
+            .child(NumericValue::new(self.value(cell).clone()).to_string())
+            .on_click(cx.listener(move |this, _, _, cx| this.store(cell, cx)))
+    }
+
+    fn render_polynomial(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let cells: Vec<_> = (MAX_DEGREE - self.degree..=MAX_DEGREE)
+            .map(|index| {
+                div()
+                    .flex_1()
+                    .flex()
+                    .flex_col()
+                    .child(POWERS[index])
+                    .child(self.render_cell(("coefficient", index), Cell::Coefficient(index), cx))
+            })
+            .collect();
+
+        div().w_full().flex().flex_row().gap(px(2.)).children(cells)
+    }
+
+    fn render_linear(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let rows: Vec<_> = (0..self.size)
+            .map(|row| {
+                let cells: Vec<_> = (0..self.size)
+                    .map(|column| {
+                        self.render_cell(
+                            ("matrix", row * MAX_SIZE + column),
+                            Cell::Matrix(row, column),
+                            cx,
+                        )
+                    })
+                    .collect();
+
+                div()
+                    .w_full()
+                    .flex()
+                    .flex_row()
+                    .gap(px(2.))
+                    .children(cells)
+                    .child("=")
+                    .child(self.render_cell(("constant", row), Cell::Constant(row), cx))
+            })
+            .collect();
+
+        div().w_full().flex().flex_col().gap(px(2.)).children(rows)
+    }
+
+    fn render_result(
+        &self,
+        index: usize,
+        value: OperandValue,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(("result", index))
+            .w_full()
+            .flex()
+            .flex_row()
+            .justify_between()
+            .child(format!("x{}", index + 1))
+            .child(
+                div()
+                    .overflow_hidden()
+                    .text_color(rgb(0xffffff))
+                    .child(value.to_string()),
+            )
+            .on_click(cx.listener(move |_, _, _, cx| cx.emit(Event::Insert(value.clone()))))
+    }
+}
+
+impl Default for SolverView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for SolverView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (kind, size): (SharedString, SharedString) = match self.kind {
+            SolverKind::Polynomial => ("Polynomial".into(), format!("deg {}", self.degree).into()),
+            SolverKind::Linear => ("Linear".into(), format!("{0}×{0}", self.size).into()),
+        };
+        let results: Vec<_> = self
+            .results
+            .clone()
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| self.render_result(index, value, cx))
+            .collect();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.))
+            .px(px(5.))
+            .text_xs()
+            .text_color(rgb(0xcccccc))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .gap(px(3.))
+                    .child(action_button("kind", kind, None, cx, |this, cx| {
+                        this.toggle_kind(cx)
+                    }))
+                    .child(action_button("size", size, None, cx, |this, cx| {
+                        this.next_size(cx)
+                    }))
+                    .child(action_button(
+                        "solve",
+                        "Solve",
+                        Some(rgb(0xff9600)),
+                        cx,
+                        |this, cx| this.solve(cx),
+                    )),
+            )
+            .map(|this| match self.kind {
+                SolverKind::Polynomial => this.child(self.render_polynomial(cx)),
+                SolverKind::Linear => this.child(self.render_linear(cx)),
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_color(rgb(0xff6060)).child(error))
+            })
+            .children(results)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Insert(OperandValue),
+}
+
+impl EventEmitter<Event> for SolverView {}
@@ -0,0 +1,110 @@
+use crate::calculator::numeric_value::{precise, round_to_places, PRECISION};
+use anyhow::{anyhow, Result};
+use dashu::{base::Abs, integer::IBig, Decimal};
+use std::ops::{Div, Mul, Sub};
+
+const SOLUTION_PLACES: usize = 15;
+
+pub fn solve(matrix: &[Vec<Decimal>], constants: &[Decimal]) -> Result<Vec<Decimal>> {
+    let size = constants.len();
+
+    if size == 0 || matrix.len() != size || matrix.iter().any(|row| row.len() != size) {
+        return Err(anyhow!("System must have as many equations as unknowns"));
+    }
+
+    let tolerance = Decimal::from_parts(IBig::ONE, -20);
+    let mut rows: Vec<Vec<Decimal>> = matrix
+        .iter()
+        .zip(constants)
+        .map(|(row, constant)| row.iter().chain([constant]).map(precise).collect())
+        .collect();
+
+    for column in 0..size {
+        let pivot = (column..size)
+            .max_by_key(|row| rows[*row][column].clone().abs())
+            .unwrap();
+
+        if rows[pivot][column].clone().abs() <= tolerance {
+            return Err(anyhow!("System has no unique solution"));
+        }
+
+        rows.swap(column, pivot);
+
+        let (done, rest) = rows.split_at_mut(column + 1);
+        let pivot_row = &done[column];
+
+        for row in rest.iter_mut() {
+            let factor = precise(&row[column]).div(&pivot_row[column]);
+
+            for (value, pivot) in row.iter_mut().zip(pivot_row).skip(column) {
+                *value = precise(value).sub(precise(&factor).mul(pivot));
+            }
+        }
+    }
+
+    let mut solution = vec![Decimal::ZERO; size];
+
+    for row in (0..size).rev() {
+        let known = (row + 1..size).fold(precise(&rows[row][size]), |acc, column| {
+            acc.sub(precise(&rows[row][column]).mul(&solution[column]))
+        });
+
+        solution[row] = known.div(&rows[row][row]).with_precision(PRECISION).value();
+    }
+
+    Ok(solution
+        .iter()
+        .map(|value| round_to_places(value, SOLUTION_PLACES))
+        .collect())
+}
+
+#[cfg(test)]
+mod test_solve {
+    use super::*;
+    use std::str::FromStr;
+
+    fn matrix(rows: &[&[i32]]) -> Vec<Vec<Decimal>> {
+        rows.iter()
+            .map(|row| row.iter().map(|value| Decimal::from(*value)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn solves_two_by_two() {
+        assert_eq!(
+            solve(&matrix(&[&[2, 1], &[1, -1]]), &[dbig!(5), dbig!(1)]).unwrap(),
+            vec![dbig!(2), dbig!(1)]
+        );
+    }
+
+    #[test]
+    fn solves_with_pivoting() {
+        assert_eq!(
+            solve(
+                &matrix(&[&[0, 2, 1], &[1, 1, 1], &[2, 1, 3]]),
+                &[dbig!(7), dbig!(6), dbig!(13)]
+            )
+            .unwrap(),
+            vec![dbig!(1), dbig!(2), dbig!(3)]
+        );
+    }
+
+    #[test]
+    fn rounds_repeating_solutions() {
+        assert_eq!(
+            solve(&matrix(&[&[3]]), &[dbig!(1)]).unwrap(),
+            vec![Decimal::from_str("0.333333333333333").unwrap()]
+        );
+    }
+
+    #[test]
+    fn rejects_singular_systems() {
+        assert!(solve(&matrix(&[&[1, 2], &[2, 4]]), &[dbig!(3), dbig!(6)]).is_err());
+    }
+
+    #[test]
+    fn rejects_mismatched_sizes() {
+        assert!(solve(&matrix(&[&[1, 2]]), &[dbig!(3)]).is_err());
+        assert!(solve(&[], &[]).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod component;
+pub mod linear;
+pub mod polynomial;
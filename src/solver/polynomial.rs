@@ -0,0 +1,297 @@
+use crate::calculator::{
+    complex::Complex,
+    numeric_value::{precise, round_to_places, PRECISION},
+};
+use anyhow::{anyhow, Result};
+use dashu::{base::Abs, integer::IBig, rational::RBig, Decimal};
+use std::ops::{Mul, Neg, Sub};
+
+const MAX_DEGREE: usize = 4;
+const MAX_ITERATIONS: usize = 500;
+const ROOT_PLACES: usize = 15;
+
+// Coefficients are stored lowest degree first.
+type Polynomial = Vec<RBig>;
+
+pub fn roots(coefficients: &[Decimal]) -> Result<Vec<Complex>> {
+    let polynomial = trim(
+        coefficients
+            .iter()
+            .rev()
+            .map(|coefficient| {
+                RBig::try_from(coefficient.clone())
+                    .map_err(|_| anyhow!("Invalid coefficient {}", coefficient))
+            })
+            .collect::<Result<Polynomial>>()?,
+    );
+
+    let degree = polynomial.len().saturating_sub(1);
+    if degree == 0 || degree > MAX_DEGREE {
+        return Err(anyhow!("Polynomial must have degree 1 to {}", MAX_DEGREE));
+    }
+
+    let mut roots = vec![];
+    for (factor, multiplicity) in square_free_factors(&polynomial) {
+        for root in solve_square_free(&factor)? {
+            roots.extend(std::iter::repeat_n(round(&root), multiplicity));
+        }
+    }
+
+    roots.sort_by(|a, b| a.re().cmp(b.re()).then(a.im().cmp(b.im())));
+
+    Ok(roots)
+}
+
+fn trim(mut polynomial: Polynomial) -> Polynomial {
+    while polynomial.last() == Some(&RBig::ZERO) {
+        polynomial.pop();
+    }
+
+    polynomial
+}
+
+fn derivative(polynomial: &Polynomial) -> Polynomial {
+    trim(
+        polynomial
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(power, coefficient)| coefficient * RBig::from(power))
+            .collect(),
+    )
+}
+
+fn subtract(a: &Polynomial, b: &Polynomial) -> Polynomial {
+    let len = a.len().max(b.len());
+
+    trim(
+        (0..len)
+            .map(|index| {
+                let a = a.get(index).cloned().unwrap_or(RBig::ZERO);
+                let b = b.get(index).cloned().unwrap_or(RBig::ZERO);
+
+                a - b
+            })
+            .collect(),
+    )
+}
+
+fn divide(a: &Polynomial, b: &Polynomial) -> (Polynomial, Polynomial) {
+    let mut remainder = a.clone();
+    let Some(lead) = b.last() else {
+        return (vec![], remainder);
+    };
+
+    if a.len() < b.len() {
+        return (vec![], remainder);
+    }
+
+    let mut quotient = vec![RBig::ZERO; a.len() - b.len() + 1];
+
+    while remainder.len() >= b.len() {
+        let shift = remainder.len() - b.len();
+        let factor = remainder.last().unwrap() / lead;
+
+        for (index, coefficient) in b.iter().enumerate() {
+            remainder[shift + index] = &remainder[shift + index] - &factor * coefficient;
+        }
+
+        quotient[shift] = factor;
+        remainder.pop();
+        remainder = trim(remainder);
+    }
+
+    (trim(quotient), remainder)
+}
+
+fn monic(polynomial: Polynomial) -> Polynomial {
+    match polynomial.last().cloned() {
+        Some(lead) => polynomial
+            .into_iter()
+            .map(|coefficient| coefficient / &lead)
+            .collect(),
+        None => polynomial,
+    }
+}
+
+fn gcd(a: &Polynomial, b: &Polynomial) -> Polynomial {
+    let mut a = a.clone();
+    let mut b = b.clone();
+
+    while !b.is_empty() {
+        let (_, remainder) = divide(&a, &b);
+        a = b;
+        b = remainder;
+    }
+
+    monic(a)
+}
+
+// Yun's algorithm: splits the polynomial into factors whose roots are all
+// simple, paired with the multiplicity those roots have in the original.
+fn square_free_factors(polynomial: &Polynomial) -> Vec<(Polynomial, usize)> {
+    let slope = derivative(polynomial);
+    let common = gcd(polynomial, &slope);
+
+    let mut b = divide(polynomial, &common).0;
+    let mut d = subtract(&divide(&slope, &common).0, &derivative(&b));
+    let mut factors = vec![];
+    let mut multiplicity = 1;
+
+    while b.len() > 1 {
+        let factor = gcd(&b, &d);
+        b = divide(&b, &factor).0;
+        let c = divide(&d, &factor).0;
+        d = subtract(&c, &derivative(&b));
+
+        if factor.len() > 1 {
+            factors.push((factor, multiplicity));
+        }
+
+        multiplicity += 1;
+    }
+
+    factors
+}
+
+fn solve_square_free(polynomial: &Polynomial) -> Result<Vec<Complex>> {
+    let coefficients: Vec<Decimal> = monic(polynomial.clone())
+        .iter()
+        .map(|coefficient| coefficient.to_float(PRECISION).value())
+        .collect();
+
+    match coefficients.len() {
+        2 => Ok(vec![Complex::from(&coefficients[0].clone().neg())]),
+        3 => {
+            let b = precise(&coefficients[1]);
+            let c = &coefficients[0];
+            let discriminant = precise(&b).mul(&b).sub(precise(c).mul(Decimal::from(4u8)));
+            let root = Complex::from(&discriminant).sqrt();
+            let two = Complex::from(&Decimal::from(2u8));
+            let minus_b = Complex::from(&b.neg());
+
+            Ok(vec![
+                minus_b.add(&root).div(&two).unwrap(),
+                minus_b.sub(&root).div(&two).unwrap(),
+            ])
+        }
+        _ => durand_kerner(&coefficients),
+    }
+}
+
+fn durand_kerner(coefficients: &[Decimal]) -> Result<Vec<Complex>> {
+    let degree = coefficients.len() - 1;
+    let tolerance = Decimal::from_parts(IBig::ONE, -25);
+    let seed = Complex::new(
+        Decimal::from_parts(IBig::from(4), -1),
+        Decimal::from_parts(IBig::from(9), -1),
+    );
+
+    let mut roots = vec![Complex::from(&Decimal::ONE)];
+    for index in 1..degree {
+        roots.push(roots[index - 1].mul(&seed));
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut converged = true;
+
+        for index in 0..degree {
+            let denominator = (0..degree)
+                .filter(|other| *other != index)
+                .fold(Complex::from(&Decimal::ONE), |acc, other| {
+                    acc.mul(&roots[index].sub(&roots[other]))
+                });
+
+            let delta = evaluate(coefficients, &roots[index])
+                .div(&denominator)
+                .ok_or_else(|| anyhow!("Failed to find roots"))?;
+
+            if delta.re().clone().abs() + delta.im().clone().abs() > tolerance {
+                converged = false;
+            }
+
+            roots[index] = roots[index].sub(&delta);
+        }
+
+        if converged {
+            return Ok(roots);
+        }
+    }
+
+    Err(anyhow!("Failed to find roots"))
+}
+
+fn evaluate(coefficients: &[Decimal], x: &Complex) -> Complex {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Complex::from(&Decimal::ZERO), |acc, coefficient| {
+            acc.mul(x).add(&Complex::from(coefficient))
+        })
+}
+
+fn round(root: &Complex) -> Complex {
+    Complex::new(
+        round_to_places(root.re(), ROOT_PLACES),
+        round_to_places(root.im(), ROOT_PLACES),
+    )
+}
+
+#[cfg(test)]
+mod test_roots {
+    use super::*;
+
+    fn solve(coefficients: &[i32]) -> Vec<String> {
+        roots(
+            &coefficients
+                .iter()
+                .map(|coefficient| Decimal::from(*coefficient))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap()
+        .iter()
+        .map(|root| match root.is_real() {
+            true => root.re().to_string(),
+            false => root.to_string(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn solves_linear() {
+        assert_eq!(solve(&[2, -3]), vec!["1.5"]);
+    }
+
+    #[test]
+    fn solves_quadratic() {
+        assert_eq!(solve(&[1, -3, 2]), vec!["1", "2"]);
+        assert_eq!(solve(&[1, 0, 1]), vec!["-i", "i"]);
+        assert_eq!(solve(&[0, 1, -3, 2]), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn solves_cubic() {
+        assert_eq!(
+            solve(&[1, 0, 0, -1]),
+            vec!["-0.5-0.866025403784439i", "-0.5+0.866025403784439i", "1"]
+        );
+        assert_eq!(solve(&[1, -6, 11, -6]), vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn solves_repeated_roots() {
+        assert_eq!(solve(&[1, -3, 3, -1]), vec!["1", "1", "1"]);
+        assert_eq!(solve(&[1, 0, 2, 0, 1]), vec!["-i", "-i", "i", "i"]);
+    }
+
+    #[test]
+    fn solves_quartic() {
+        assert_eq!(solve(&[1, 0, -5, 0, 4]), vec!["-2", "-1", "1", "2"]);
+    }
+
+    #[test]
+    fn rejects_constants_and_high_degrees() {
+        assert!(roots(&[dbig!(0), dbig!(5)]).is_err());
+        assert!(roots(&[dbig!(1), dbig!(0), dbig!(0), dbig!(0), dbig!(0), dbig!(1)]).is_err());
+    }
+}
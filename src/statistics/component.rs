@@ -0,0 +1,239 @@
+use super::dataset::{DataPoint, DataSet};
+use crate::{action_button::action_button, calculator::numeric_value::NumericValue};
+use dashu::Decimal;
+use gpui::{div, prelude::*, px, rgb, Context, EventEmitter, SharedString, Window};
+
+const VISIBLE_POINTS: usize = 8;
+
+pub struct StatisticsView {
+    data: DataSet,
+    // The point that the next added or pasted value replaces.
+    selected: Option<usize>,
+    error: Option<SharedString>,
+}
+
+impl StatisticsView {
+    pub fn new() -> Self {
+        StatisticsView {
+            data: DataSet::default(),
+            selected: None,
+            error: None,
+        }
+    }
+
+    pub fn add(&mut self, value: &NumericValue, cx: &mut Context<Self>) {
+        let y = value.val().clone();
+
+        match self.selected.take() {
+            // A typed value replaces the y of a selected pair and keeps its x.
+            Some(index) => {
+                let x = self.data.points()[index].x.clone();
+                self.data.replace(index, DataPoint { x, y });
+            }
+            None => self.data.push(DataPoint { x: None, y }),
+        }
+
+        self.error = None;
+        cx.notify();
+    }
+
+    pub fn paste(&mut self, text: &str, cx: &mut Context<Self>) {
+        match (DataSet::parse(text), self.selected) {
+            (Ok(mut points), Some(index)) if points.len() == 1 => {
+                self.data.replace(index, points.remove(0));
+                self.selected = None;
+                self.error = None;
+            }
+            (Ok(_), Some(_)) => {
+                self.error = Some("Paste a single value to replace the selected one".into());
+            }
+            (Ok(points), None) => {
+                self.data.extend(points);
+                self.error = None;
+            }
+            (Err(err), _) => self.error = Some(err.to_string().into()),
+        }
+
+        cx.notify();
+    }
+
+    fn paste_clipboard(&mut self, cx: &mut Context<Self>) {
+        if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+            self.paste(&text, cx);
+        }
+    }
+
+    fn select(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.selected = match self.selected {
+            Some(selected) if selected == index => None,
+            _ => Some(index),
+        };
+        cx.notify();
+    }
+
+    fn remove_selected(&mut self, cx: &mut Context<Self>) {
+        if let Some(index) = self.selected.take() {
+            self.data.remove(index);
+        }
+        cx.notify();
+    }
+
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.data.clear();
+        self.selected = None;
+        self.error = None;
+        cx.notify();
+    }
+
+    fn results(&self) -> Vec<(&'static str, Decimal)> {
+        let mut results = vec![];
+
+        if let Some(summary) = self.data.summary() {
+            results.push(("Σ", summary.sum));
+            results.push(("x̄", summary.mean));
+            results.push(("med", summary.median));
+            results.extend(summary.modes.into_iter().map(|mode| ("mode", mode)));
+            results.push(("min", summary.min));
+            results.push(("max", summary.max));
+            results.push(("σ", summary.population_std_dev));
+            results.push(("σ²", summary.population_variance));
+            if let Some(sample_std_dev) = summary.sample_std_dev {
+                results.push(("s", sample_std_dev));
+            }
+            if let Some(sample_variance) = summary.sample_variance {
+                results.push(("s²", sample_variance));
+            }
+        }
+
+        if let Some(regression) = self.data.regression() {
+            results.push(("a", regression.intercept));
+            results.push(("b", regression.slope));
+            results.push(("r", regression.correlation));
+        }
+
+        results
+    }
+
+    fn render_point(&self, index: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let point = &self.data.points()[index];
+        let label: SharedString = match &point.x {
+            Some(x) => format!("{};{}", x, point.y).into(),
+            None => point.y.to_string().into(),
+        };
+
+        let bg = match self.selected == Some(index) {
+            true => rgb(0xff9600),
+            false => rgb(0x3a3a3a),
+        };
+
+        div()
+            .id(("point", index))
+            .px(px(3.))
+            .rounded_sm()
+            .bg(bg)
+            .child(label)
+            .on_click(cx.listener(move |this, _, _, cx| this.select(index, cx)))
+    }
+
+    fn render_result(
+        &self,
+        index: usize,
+        label: &'static str,
+        value: Decimal,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let value = NumericValue::new(value);
+
+        div()
+            .id(("result", index))
+            .w_full()
+            .flex()
+            .flex_row()
+            .justify_between()
+            .child(label)
+            .child(
+                div()
+                    .overflow_hidden()
+                    .text_color(rgb(0xffffff))
+                    .child(value.to_string()),
+            )
+            .on_click(cx.listener(move |_, _, _, cx| cx.emit(Event::Insert(value.clone()))))
+    }
+}
+
+impl Default for StatisticsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for StatisticsView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let len = self.data.points().len();
+        let points: Vec<_> = (len.saturating_sub(VISIBLE_POINTS)..len)
+            .map(|index| self.render_point(index, cx))
+            .collect();
+        let results: Vec<_> = self
+            .results()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (label, value))| self.render_result(index, label, value, cx))
+            .collect();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.))
+            .px(px(5.))
+            .text_xs()
+            .text_color(rgb(0xcccccc))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .justify_between()
+                    .child(format!("n={}", len))
+                    .child(
+                        div()
+                            .flex()
+                            .flex_row()
+                            .gap(px(3.))
+                            .child(action_button("add", "Σ+", None, cx, |_, cx| {
+                                cx.emit(Event::Add)
+                            }))
+                            .child(action_button("paste", "Paste", None, cx, |this, cx| {
+                                this.paste_clipboard(cx)
+                            }))
+                            .when(self.selected.is_some(), |this| {
+                                this.child(action_button("remove", "✕", None, cx, |this, cx| {
+                                    this.remove_selected(cx)
+                                }))
+                            })
+                            .child(action_button("clear", "⌫", None, cx, |this, cx| {
+                                this.clear(cx)
+                            })),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_color(rgb(0xff6060)).child(error))
+            })
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .flex_wrap()
+                    .gap(px(2.))
+                    .children(points),
+            )
+            .children(results)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Add,
+    Insert(NumericValue),
+}
+
+impl EventEmitter<Event> for StatisticsView {}
@@ -0,0 +1,411 @@
+use crate::calculator::numeric_value::{precise, PRECISION};
+use anyhow::{anyhow, Result};
+use dashu::{base::SquareRoot, Decimal};
+use std::{
+    ops::{Add, Div, Mul, Sub},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataPoint {
+    pub x: Option<Decimal>,
+    pub y: Decimal,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataSet {
+    points: Vec<DataPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub sum: Decimal,
+    pub mean: Decimal,
+    pub median: Decimal,
+    pub modes: Vec<Decimal>,
+    pub min: Decimal,
+    pub max: Decimal,
+    pub population_variance: Decimal,
+    pub population_std_dev: Decimal,
+    pub sample_variance: Option<Decimal>,
+    pub sample_std_dev: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub slope: Decimal,
+    pub intercept: Decimal,
+    pub correlation: Decimal,
+}
+
+impl DataSet {
+    pub fn parse(text: &str) -> Result<Vec<DataPoint>> {
+        let mut points = vec![];
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some((x, y)) = line.split_once(['\t', ';']) {
+                points.push(DataPoint {
+                    x: Some(parse_value(x)?),
+                    y: parse_value(y)?,
+                });
+                continue;
+            }
+
+            for value in line.split([',', ' ']).filter(|value| !value.is_empty()) {
+                points.push(DataPoint {
+                    x: None,
+                    y: parse_value(value)?,
+                });
+            }
+        }
+
+        Ok(points)
+    }
+
+    pub fn points(&self) -> &[DataPoint] {
+        &self.points
+    }
+
+    pub fn push(&mut self, point: DataPoint) {
+        self.points.push(point);
+    }
+
+    pub fn extend(&mut self, points: Vec<DataPoint>) {
+        self.points.extend(points);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.points.len() {
+            self.points.remove(index);
+        }
+    }
+
+    // Returns false when there is no point at `index`.
+    pub fn replace(&mut self, index: usize, point: DataPoint) -> bool {
+        match self.points.get_mut(index) {
+            Some(existing) => {
+                *existing = point;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    pub fn summary(&self) -> Option<Summary> {
+        if self.points.is_empty() {
+            return None;
+        }
+
+        let count = self.points.len();
+        let mut values: Vec<Decimal> = self.points.iter().map(|point| point.y.clone()).collect();
+        values.sort();
+
+        let sum = total(&values);
+        let mean = divide(&sum, count);
+
+        let median = if count % 2 == 1 {
+            values[count / 2].clone()
+        } else {
+            divide(&values[count / 2 - 1].clone().add(&values[count / 2]), 2)
+        };
+
+        let squares = total(
+            &values
+                .iter()
+                .map(|value| {
+                    let deviation = precise(value).sub(&mean);
+                    precise(&deviation).mul(&deviation)
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let population_variance = divide(&squares, count);
+        let sample_variance = (count > 1).then(|| divide(&squares, count - 1));
+
+        Some(Summary {
+            count,
+            mean,
+            median,
+            modes: modes(&values),
+            min: values[0].clone(),
+            max: values[count - 1].clone(),
+            population_std_dev: sqrt(&population_variance),
+            sample_std_dev: sample_variance.as_ref().map(sqrt),
+            population_variance,
+            sample_variance,
+            sum,
+        })
+    }
+
+    pub fn regression(&self) -> Option<Regression> {
+        let count = self.points.len();
+        if count < 2 {
+            return None;
+        }
+
+        let paired = self.points.iter().all(|point| point.x.is_some());
+        let xs: Vec<Decimal> = self
+            .points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| match (&point.x, paired) {
+                (Some(x), true) => x.clone(),
+                _ => Decimal::from(index + 1),
+            })
+            .collect();
+        let ys: Vec<Decimal> = self.points.iter().map(|point| point.y.clone()).collect();
+
+        let mean_x = divide(&total(&xs), count);
+        let mean_y = divide(&total(&ys), count);
+
+        let mut sxx = Decimal::ZERO;
+        let mut syy = Decimal::ZERO;
+        let mut sxy = Decimal::ZERO;
+
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            let dx = precise(x).sub(&mean_x);
+            let dy = precise(y).sub(&mean_y);
+
+            sxx = precise(&sxx).add(precise(&dx).mul(&dx));
+            syy = precise(&syy).add(precise(&dy).mul(&dy));
+            sxy = precise(&sxy).add(precise(&dx).mul(&dy));
+        }
+
+        if sxx == Decimal::ZERO {
+            return None;
+        }
+
+        let slope = precise(&sxy).div(&sxx);
+        let intercept = precise(&mean_y).sub(precise(&slope).mul(&mean_x));
+        let correlation = if syy == Decimal::ZERO {
+            Decimal::ZERO
+        } else {
+            precise(&sxy).div(sqrt(&precise(&sxx).mul(&syy)))
+        };
+
+        Some(Regression {
+            slope: precise(&slope),
+            intercept: precise(&intercept),
+            correlation: precise(&correlation),
+        })
+    }
+}
+
+fn parse_value(value: &str) -> Result<Decimal> {
+    let value = value.trim();
+
+    Decimal::from_str(value).map_err(|err| anyhow!("Invalid value {}: {}", value, err))
+}
+
+fn total(values: &[Decimal]) -> Decimal {
+    values
+        .iter()
+        .fold(Decimal::ZERO, |acc, value| precise(&acc).add(value))
+}
+
+fn divide(value: &Decimal, count: usize) -> Decimal {
+    precise(value)
+        .div(Decimal::from(count))
+        .with_precision(PRECISION)
+        .value()
+}
+
+fn sqrt(value: &Decimal) -> Decimal {
+    if *value <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+
+    precise(value).sqrt()
+}
+
+fn modes(sorted: &[Decimal]) -> Vec<Decimal> {
+    let mut runs: Vec<(Decimal, usize)> = vec![];
+
+    for value in sorted {
+        match runs.last_mut() {
+            Some((last, count)) if last == value => *count += 1,
+            _ => runs.push((value.clone(), 1)),
+        }
+    }
+
+    let highest = runs.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    if highest < 2 {
+        return vec![];
+    }
+
+    runs.into_iter()
+        .filter(|(_, count)| *count == highest)
+        .map(|(value, _)| value)
+        .collect()
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn parses_values_separated_by_commas_spaces_and_lines() {
+        let points = DataSet::parse("1, 2 3\n4.5\n\n").unwrap();
+
+        assert_eq!(
+            points
+                .iter()
+                .map(|point| point.y.clone())
+                .collect::<Vec<_>>(),
+            vec![dbig!(1), dbig!(2), dbig!(3), dbig!(4.5)]
+        );
+    }
+
+    #[test]
+    fn parses_pairs_separated_by_tabs_or_semicolons() {
+        let points = DataSet::parse("1\t2\n3; 4\n").unwrap();
+
+        assert_eq!(
+            points,
+            vec![
+                DataPoint {
+                    x: Some(dbig!(1)),
+                    y: dbig!(2)
+                },
+                DataPoint {
+                    x: Some(dbig!(3)),
+                    y: dbig!(4)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(DataSet::parse("1, two").is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_summary {
+    use super::*;
+
+    fn data(text: &str) -> DataSet {
+        let mut data = DataSet::default();
+        data.extend(DataSet::parse(text).unwrap());
+
+        data
+    }
+
+    #[test]
+    fn summarises_values() {
+        let summary = data("2 4 4 4 5 5 7 9").summary().unwrap();
+
+        assert_eq!(summary.count, 8);
+        assert_eq!(summary.sum, dbig!(40));
+        assert_eq!(summary.mean, dbig!(5));
+        assert_eq!(summary.median, dbig!(4.5));
+        assert_eq!(summary.modes, vec![dbig!(4)]);
+        assert_eq!(summary.min, dbig!(2));
+        assert_eq!(summary.max, dbig!(9));
+        assert_eq!(summary.population_variance, dbig!(4));
+        assert_eq!(summary.population_std_dev, dbig!(2));
+        assert_eq!(
+            summary.sample_variance.unwrap().to_string()[..10],
+            *"4.57142857"
+        );
+    }
+
+    #[test]
+    fn median_of_odd_count_is_middle_value() {
+        assert_eq!(data("9 1 5").summary().unwrap().median, dbig!(5));
+    }
+
+    #[test]
+    fn has_no_mode_when_values_are_unique() {
+        assert!(data("1 2 3").summary().unwrap().modes.is_empty());
+    }
+
+    #[test]
+    fn single_value_has_no_sample_variance() {
+        let summary = data("3").summary().unwrap();
+
+        assert_eq!(summary.sample_variance, None);
+        assert_eq!(summary.population_variance, dbig!(0));
+    }
+
+    #[test]
+    fn empty_data_has_no_summary() {
+        assert_eq!(DataSet::default().summary(), None);
+    }
+
+    #[test]
+    fn removes_values() {
+        let mut data = data("1 2 3");
+        data.remove(1);
+        data.remove(5);
+
+        assert_eq!(data.summary().unwrap().sum, dbig!(4));
+    }
+
+    #[test]
+    fn replaces_values() {
+        let mut data = data("1 2 3");
+        let point = DataPoint {
+            x: None,
+            y: dbig!(7),
+        };
+
+        assert!(data.replace(1, point.clone()));
+        assert!(!data.replace(3, point));
+        assert_eq!(
+            data.points()
+                .iter()
+                .map(|point| &point.y)
+                .collect::<Vec<_>>(),
+            vec![&dbig!(1), &dbig!(7), &dbig!(3)]
+        );
+        assert_eq!(data.summary().unwrap().sum, dbig!(11));
+    }
+}
+
+#[cfg(test)]
+mod test_regression {
+    use super::*;
+
+    fn data(text: &str) -> DataSet {
+        let mut data = DataSet::default();
+        data.extend(DataSet::parse(text).unwrap());
+
+        data
+    }
+
+    #[test]
+    fn fits_paired_values() {
+        let regression = data("1;3\n2;5\n3;7\n").regression().unwrap();
+
+        assert_eq!(regression.slope, dbig!(2));
+        assert_eq!(regression.intercept, dbig!(1));
+        assert_eq!(regression.correlation, dbig!(1));
+    }
+
+    #[test]
+    fn fits_values_against_position() {
+        let regression = data("10 8 6").regression().unwrap();
+
+        assert_eq!(regression.slope, dbig!(-2));
+        assert_eq!(regression.intercept, dbig!(12));
+        assert_eq!(regression.correlation, dbig!(-1));
+    }
+
+    #[test]
+    fn needs_two_distinct_points() {
+        assert_eq!(data("5").regression(), None);
+        assert_eq!(data("1;3\n1;4\n").regression(), None);
+    }
+}
@@ -0,0 +1,61 @@
+// Index arithmetic for the tabs of a workspace window, kept apart from the
+// view so it can be tested without a window.
+
+// The tab to activate after closing the active one, or None when it was the
+// last tab and the window should close instead.
+pub fn after_close(active: usize, len: usize) -> Option<usize> {
+    if len <= 1 {
+        return None;
+    }
+
+    Some(active.min(len - 2))
+}
+
+pub fn next(active: usize, len: usize) -> usize {
+    (active + 1) % len
+}
+
+pub fn previous(active: usize, len: usize) -> usize {
+    (active + len - 1) % len
+}
+
+// Clamps an index from a click or drop that may refer to a closed tab.
+pub fn clamp(index: usize, len: usize) -> usize {
+    index.min(len.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod test_tabs {
+    use super::*;
+
+    #[test]
+    fn closes_the_active_tab() {
+        // Closing a middle tab activates the one that moves into its place.
+        assert_eq!(after_close(1, 3), Some(1));
+        assert_eq!(after_close(0, 2), Some(0));
+        // Closing the rightmost tab activates its left neighbour.
+        assert_eq!(after_close(2, 3), Some(1));
+    }
+
+    #[test]
+    fn closing_the_last_tab_closes_the_window() {
+        assert_eq!(after_close(0, 1), None);
+        assert_eq!(after_close(0, 0), None);
+    }
+
+    #[test]
+    fn cycles_through_tabs() {
+        assert_eq!(next(0, 3), 1);
+        assert_eq!(next(2, 3), 0);
+        assert_eq!(previous(0, 3), 2);
+        assert_eq!(previous(2, 3), 1);
+        assert_eq!(next(0, 1), 0);
+    }
+
+    #[test]
+    fn clamps_stale_indices() {
+        assert_eq!(clamp(4, 3), 2);
+        assert_eq!(clamp(1, 3), 1);
+        assert_eq!(clamp(0, 0), 0);
+    }
+}
@@ -0,0 +1,223 @@
+use super::{
+    export::ExportFormat,
+    record::{Tape, TapeLine},
+};
+use crate::{
+    action_button::action_button,
+    calculator::{
+        calculation::{Calculation, Operation},
+        numeric_value::NumericValue,
+    },
+    paths,
+};
+use gpui::{div, prelude::*, px, rgb, Context, EventEmitter, Keystroke, SharedString, Window};
+use std::fs;
+
+const VISIBLE_LINES: usize = 6;
+
+pub struct TapeView {
+    tape: Tape,
+    editing: Option<(usize, String)>,
+    error: Option<SharedString>,
+}
+
+impl TapeView {
+    pub fn new() -> Self {
+        TapeView {
+            tape: Tape::default(),
+            editing: None,
+            error: None,
+        }
+    }
+
+    pub fn record_entry(
+        &mut self,
+        value: NumericValue,
+        operation: Operation,
+        replace: bool,
+        cx: &mut Context<Self>,
+    ) {
+        self.tape.record_entry(value, operation, replace);
+        cx.notify();
+    }
+
+    pub fn record_calculate(
+        &mut self,
+        calculation: &Calculation,
+        total: NumericValue,
+        cx: &mut Context<Self>,
+    ) {
+        self.tape.record_calculate(calculation, total);
+        cx.notify();
+    }
+
+    pub fn record_subtotal(&mut self, value: NumericValue, cx: &mut Context<Self>) {
+        self.tape.record_subtotal(value);
+        cx.notify();
+    }
+
+    pub fn record_total(&mut self, value: NumericValue, cx: &mut Context<Self>) {
+        self.tape.record_total(value);
+        cx.notify();
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    pub fn edit_key(&mut self, keystroke: &Keystroke, cx: &mut Context<Self>) {
+        let Some((index, text)) = self.editing.as_mut() else {
+            return;
+        };
+
+        match keystroke.key.as_str() {
+            "enter" => {
+                let index = *index;
+                let text = text.clone();
+
+                self.tape.annotate(index, &text);
+                self.editing = None;
+            }
+            "escape" => self.editing = None,
+            "backspace" => {
+                text.pop();
+            }
+            _ => {
+                if let Some(key_char) = &keystroke.key_char {
+                    text.push_str(key_char);
+                }
+            }
+        }
+
+        cx.notify();
+    }
+
+    fn start_editing(&mut self, index: usize, cx: &mut Context<Self>) {
+        let annotation = self.tape.entries()[index]
+            .annotation
+            .clone()
+            .unwrap_or_default();
+
+        self.editing = Some((index, annotation));
+        cx.notify();
+    }
+
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.tape.clear();
+        self.editing = None;
+        cx.notify();
+    }
+
+    fn export(&mut self, format: ExportFormat, cx: &mut Context<Self>) {
+        let contents = format.render(&self.tape);
+        let directory = dirs::document_dir().unwrap_or_else(paths::config_dir);
+        let path = cx.prompt_for_new_path(&directory);
+
+        cx.spawn(|this, mut cx| async move {
+            let error = match path.await {
+                Ok(Ok(Some(path))) => {
+                    let path = path.with_extension(format.extension());
+
+                    fs::write(&path, contents)
+                        .err()
+                        .map(|err| format!("Failed to export tape to {}: {}", path.display(), err))
+                }
+                Ok(Err(err)) => Some(format!("Failed to choose a file: {}", err)),
+                _ => None,
+            };
+
+            this.update(&mut cx, |this, cx| {
+                this.error = error.map(Into::into);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn render_line(&self, index: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry = &self.tape.entries()[index];
+        let is_result = !matches!(entry.line, TapeLine::Entry(_, _));
+
+        let annotation: SharedString = match &self.editing {
+            Some((editing, text)) if *editing == index => format!("{}▏", text).into(),
+            _ => entry.annotation.clone().unwrap_or_default().into(),
+        };
+
+        div()
+            .id(("tape-line", index))
+            .w_full()
+            .flex()
+            .flex_row()
+            .justify_between()
+            .gap(px(4.))
+            .when(is_result, |this| this.text_color(rgb(0xffffff)))
+            .child(div().overflow_hidden().child(annotation))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .gap(px(4.))
+                    .child(entry.line.value().to_string())
+                    .child(div().w(px(10.)).child(entry.line.marker())),
+            )
+            .on_click(cx.listener(move |this, _, _, cx| this.start_editing(index, cx)))
+    }
+}
+
+impl Default for TapeView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Render for TapeView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let len = self.tape.entries().len();
+        let lines: Vec<_> = (len.saturating_sub(VISIBLE_LINES)..len)
+            .map(|index| self.render_line(index, cx))
+            .collect();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.))
+            .px(px(5.))
+            .text_xs()
+            .text_color(rgb(0xcccccc))
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .gap(px(3.))
+                    .child(action_button("subtotal", "◇", None, cx, |_, cx| {
+                        cx.emit(Event::Subtotal)
+                    }))
+                    .child(action_button("txt", "TXT", None, cx, |this, cx| {
+                        this.export(ExportFormat::Text, cx)
+                    }))
+                    .child(action_button("csv", "CSV", None, cx, |this, cx| {
+                        this.export(ExportFormat::Csv, cx)
+                    }))
+                    .child(action_button("html", "HTML", None, cx, |this, cx| {
+                        this.export(ExportFormat::Html, cx)
+                    }))
+                    .child(action_button("clear", "⌫", None, cx, |this, cx| {
+                        this.clear(cx)
+                    })),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_color(rgb(0xff6060)).child(error))
+            })
+            .when(self.tape.is_empty(), |this| this.child("Tape is empty"))
+            .children(lines)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Subtotal,
+}
+
+impl EventEmitter<Event> for TapeView {}
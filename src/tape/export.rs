@@ -0,0 +1,192 @@
+use super::record::{Tape, TapeLine};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Text,
+    Csv,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    pub fn render(&self, tape: &Tape) -> String {
+        match self {
+            ExportFormat::Text => to_text(tape),
+            ExportFormat::Csv => to_csv(tape),
+            ExportFormat::Html => to_html(tape),
+        }
+    }
+}
+
+fn kind(line: &TapeLine) -> &'static str {
+    match line {
+        TapeLine::Entry(_, _) => "entry",
+        TapeLine::Subtotal(_) => "subtotal",
+        TapeLine::Total(_) => "total",
+    }
+}
+
+fn to_text(tape: &Tape) -> String {
+    let width = tape
+        .entries()
+        .iter()
+        .map(|entry| entry.line.value().to_string().chars().count())
+        .max()
+        .unwrap_or(0);
+
+    tape.entries()
+        .iter()
+        .map(|entry| {
+            let line = format!(
+                "{:>width$} {}",
+                entry.line.value().to_string(),
+                entry.line.marker(),
+                width = width
+            );
+
+            match &entry.annotation {
+                Some(annotation) => format!("{}  {}\n", line, annotation),
+                None => format!("{}\n", line),
+            }
+        })
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_csv(tape: &Tape) -> String {
+    let mut csv = String::from("kind,value,operation,annotation\n");
+
+    for entry in tape.entries() {
+        let operation = match &entry.line {
+            TapeLine::Entry(_, operation) => operation.to_string(),
+            _ => String::new(),
+        };
+
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            kind(&entry.line),
+            entry.line.value(),
+            csv_field(&operation),
+            csv_field(entry.annotation.as_deref().unwrap_or_default())
+        ));
+    }
+
+    csv
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn to_html(tape: &Tape) -> String {
+    let rows: String = tape
+        .entries()
+        .iter()
+        .map(|entry| {
+            format!(
+                "<tr class=\"{}\"><td class=\"value\">{}</td><td>{}</td><td>{}</td></tr>\n",
+                kind(&entry.line),
+                html_escape(&entry.line.value().to_string()),
+                html_escape(&entry.line.marker()),
+                html_escape(entry.annotation.as_deref().unwrap_or_default())
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Calculator tape</title>
+<style>
+body {{ font-family: monospace; }}
+table {{ border-collapse: collapse; }}
+td {{ padding: 2px 8px; }}
+td.value {{ text-align: right; }}
+tr.subtotal td, tr.total td {{ border-top: 1px solid black; font-weight: bold; }}
+@media print {{ body {{ margin: 0; }} }}
+</style>
+</head>
+<body>
+<table>
+{}</table>
+</body>
+</html>
+"#,
+        rows
+    )
+}
+
+#[cfg(test)]
+mod test_export {
+    use super::*;
+    use crate::calculator::{calculation::Operation, numeric_value::NumericValue};
+
+    fn tape() -> Tape {
+        let mut tape = Tape::default();
+        tape.record_entry(NumericValue::new(dbig!(125)), Operation::Addition, false);
+        tape.record_entry(NumericValue::new(dbig!(5)), Operation::Equals, false);
+        tape.record_total(NumericValue::new(dbig!(130)));
+        tape.annotate(0, "rent, \"May\"");
+
+        tape
+    }
+
+    #[test]
+    fn exports_text() {
+        assert_eq!(
+            ExportFormat::Text.render(&tape()),
+            "125 +  rent, \"May\"\n  5 ＝\n130 T\n"
+        );
+    }
+
+    #[test]
+    fn exports_csv() {
+        assert_eq!(
+            ExportFormat::Csv.render(&tape()),
+            "kind,value,operation,annotation\n\
+             entry,125,+,\"rent, \"\"May\"\"\"\n\
+             entry,5,＝,\n\
+             total,130,,\n"
+        );
+    }
+
+    #[test]
+    fn exports_escaped_html() {
+        let html = ExportFormat::Html.render(&tape());
+
+        assert!(html.contains(
+            "<tr class=\"entry\"><td class=\"value\">125</td><td>+</td><td>rent, &quot;May&quot;</td></tr>"
+        ));
+        assert!(html
+            .contains("<tr class=\"total\"><td class=\"value\">130</td><td>T</td><td></td></tr>"));
+    }
+
+    #[test]
+    fn exports_empty_tape() {
+        assert_eq!(ExportFormat::Text.render(&Tape::default()), "");
+        assert_eq!(
+            ExportFormat::Csv.render(&Tape::default()),
+            "kind,value,operation,annotation\n"
+        );
+    }
+}
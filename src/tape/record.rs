@@ -0,0 +1,199 @@
+use crate::calculator::{
+    calculation::{Calculation, OperandValue, Operation},
+    numeric_value::NumericValue,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TapeLine {
+    Entry(NumericValue, Operation),
+    Subtotal(NumericValue),
+    Total(NumericValue),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TapeEntry {
+    pub line: TapeLine,
+    pub annotation: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Tape {
+    entries: Vec<TapeEntry>,
+}
+
+impl Tape {
+    pub fn entries(&self) -> &[TapeEntry] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn record_entry(&mut self, value: NumericValue, operation: Operation, replace: bool) {
+        if replace {
+            if let Some(TapeEntry {
+                line: TapeLine::Entry(last_value, last_operation),
+                ..
+            }) = self.entries.last_mut()
+            {
+                if *last_value == value {
+                    *last_operation = operation;
+                    return;
+                }
+            }
+        }
+
+        self.push(TapeLine::Entry(value, operation));
+    }
+
+    // Records `=` pressed on `calculation`, giving `total`. Pressing it again
+    // on a result records the operation and operand it repeats.
+    pub fn record_calculate(&mut self, calculation: &Calculation, total: NumericValue) {
+        let line = match calculation.repeated_operation() {
+            Some((operation, OperandValue::Number(operand))) => TapeLine::Entry(operand, operation),
+            _ => TapeLine::Entry(calculation.current_value(), Operation::Equals),
+        };
+
+        self.push(line);
+        self.push(TapeLine::Total(total));
+    }
+
+    pub fn record_subtotal(&mut self, value: NumericValue) {
+        self.push(TapeLine::Subtotal(value));
+    }
+
+    pub fn record_total(&mut self, value: NumericValue) {
+        self.push(TapeLine::Total(value));
+    }
+
+    pub fn annotate(&mut self, index: usize, annotation: &str) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            let annotation = annotation.trim();
+
+            entry.annotation = if annotation.is_empty() {
+                None
+            } else {
+                Some(annotation.to_string())
+            };
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn push(&mut self, line: TapeLine) {
+        self.entries.push(TapeEntry {
+            line,
+            annotation: None,
+        });
+    }
+}
+
+impl TapeLine {
+    pub fn value(&self) -> &NumericValue {
+        match self {
+            TapeLine::Entry(value, _) | TapeLine::Subtotal(value) | TapeLine::Total(value) => value,
+        }
+    }
+
+    pub fn marker(&self) -> String {
+        match self {
+            TapeLine::Entry(_, operation) => operation.to_string(),
+            TapeLine::Subtotal(_) => "◇".to_string(),
+            TapeLine::Total(_) => "T".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_record {
+    use super::*;
+
+    #[test]
+    fn records_entries_in_order() {
+        let mut tape = Tape::default();
+        tape.record_entry(NumericValue::new(dbig!(5)), Operation::Addition, false);
+        tape.record_entry(NumericValue::new(dbig!(3)), Operation::Equals, false);
+        tape.record_total(NumericValue::new(dbig!(8)));
+
+        assert_eq!(
+            tape.entries()
+                .iter()
+                .map(|entry| entry.line.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                TapeLine::Entry(NumericValue::new(dbig!(5)), Operation::Addition),
+                TapeLine::Entry(NumericValue::new(dbig!(3)), Operation::Equals),
+                TapeLine::Total(NumericValue::new(dbig!(8))),
+            ]
+        );
+    }
+
+    #[test]
+    fn records_repeated_equals() {
+        let mut tape = Tape::default();
+        let mut calculation = Calculation::default();
+        calculation.append_number(5);
+        calculation.append_operation(Operation::Addition);
+        tape.record_entry(NumericValue::new(dbig!(5)), Operation::Addition, false);
+        calculation.append_number(3);
+
+        for _ in 0..2 {
+            let before = calculation.clone();
+            calculation.calculate();
+            tape.record_calculate(&before, calculation.current_value());
+        }
+
+        assert_eq!(
+            tape.entries()
+                .iter()
+                .map(|entry| entry.line.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                TapeLine::Entry(NumericValue::new(dbig!(5)), Operation::Addition),
+                TapeLine::Entry(NumericValue::new(dbig!(3)), Operation::Equals),
+                TapeLine::Total(NumericValue::new(dbig!(8))),
+                TapeLine::Entry(NumericValue::new(dbig!(3)), Operation::Addition),
+                TapeLine::Total(NumericValue::new(dbig!(11))),
+            ]
+        );
+    }
+
+    #[test]
+    fn replaces_overridden_operation() {
+        let mut tape = Tape::default();
+        tape.record_entry(NumericValue::new(dbig!(5)), Operation::Addition, false);
+        tape.record_entry(NumericValue::new(dbig!(5)), Operation::Subtraction, true);
+
+        assert_eq!(
+            tape.entries(),
+            &[TapeEntry {
+                line: TapeLine::Entry(NumericValue::new(dbig!(5)), Operation::Subtraction),
+                annotation: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn annotates_lines() {
+        let mut tape = Tape::default();
+        tape.record_subtotal(NumericValue::new(dbig!(5)));
+        tape.annotate(0, "  rent ");
+
+        assert_eq!(tape.entries()[0].annotation, Some("rent".to_string()));
+
+        tape.annotate(0, "");
+
+        assert_eq!(tape.entries()[0].annotation, None);
+    }
+
+    #[test]
+    fn ignores_annotation_out_of_range() {
+        let mut tape = Tape::default();
+        tape.annotate(3, "rent");
+
+        assert!(tape.is_empty());
+    }
+}
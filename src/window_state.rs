@@ -0,0 +1,153 @@
+use anyhow::Result;
+use std::{fs, path::Path};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+// Frames are kept relative to the origin of the display they were on, so a
+// window comes back to the same spot even when displays are rearranged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    #[serde(default)]
+    pub display: Option<String>,
+    #[serde(default)]
+    pub frame: Option<Frame>,
+    #[serde(default)]
+    pub mini_frame: Option<Frame>,
+    #[serde(default)]
+    pub mini: bool,
+}
+
+impl WindowState {
+    pub fn parse(json: &str) -> Result<WindowState> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load(path: &Path) -> Result<WindowState> {
+        if !path.exists() {
+            return Ok(WindowState::default());
+        }
+
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    pub fn remember(&mut self, mini: bool, frame: Frame, display: Frame, id: Option<String>) {
+        let relative = Frame {
+            x: frame.x - display.x,
+            y: frame.y - display.y,
+            ..frame
+        };
+
+        match mini {
+            true => self.mini_frame = Some(relative),
+            false => self.frame = Some(relative),
+        }
+        self.display = id;
+    }
+
+    // Places the saved frame on `display`, shrinking and moving it as needed
+    // so it stays fully visible when the display is smaller than before.
+    pub fn restore(&self, mini: bool, display: Frame) -> Option<Frame> {
+        let saved = match mini {
+            true => self.mini_frame?,
+            false => self.frame?,
+        };
+
+        let width = saved.width.min(display.width);
+        let height = saved.height.min(display.height);
+
+        Some(Frame {
+            x: display.x + saved.x.clamp(0., display.width - width),
+            y: display.y + saved.y.clamp(0., display.height - height),
+            width,
+            height,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_window_state {
+    use super::*;
+
+    fn frame(x: f32, y: f32, width: f32, height: f32) -> Frame {
+        Frame {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn restores_relative_to_display() {
+        let mut state = WindowState::default();
+        let display = frame(1920., 0., 1920., 1080.);
+        state.remember(
+            false,
+            frame(2020., 50., 198., 350.),
+            display,
+            Some("b".into()),
+        );
+
+        assert_eq!(state.display.as_deref(), Some("b"));
+        assert_eq!(
+            state.restore(false, display),
+            Some(frame(2020., 50., 198., 350.))
+        );
+        assert_eq!(
+            state.restore(false, frame(0., 0., 1440., 900.)),
+            Some(frame(100., 50., 198., 350.))
+        );
+        assert_eq!(state.restore(true, display), None);
+    }
+
+    #[test]
+    fn keeps_frames_on_smaller_displays() {
+        let mut state = WindowState::default();
+        let display = frame(0., 0., 2560., 1440.);
+        state.remember(false, frame(2400., 1200., 198., 350.), display, None);
+        state.remember(true, frame(-50., 10., 198., 270.), display, None);
+
+        assert_eq!(
+            state.restore(false, frame(0., 0., 1280., 300.)),
+            Some(frame(1082., 0., 198., 300.))
+        );
+        assert_eq!(
+            state.restore(true, display),
+            Some(frame(0., 10., 198., 270.))
+        );
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let mut state = WindowState {
+            mini: true,
+            ..Default::default()
+        };
+        state.remember(
+            true,
+            frame(10., 20., 198., 270.),
+            frame(0., 0., 800., 600.),
+            Some("display".into()),
+        );
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(WindowState::parse(&json).unwrap(), state);
+        assert_eq!(WindowState::parse("{}").unwrap(), WindowState::default());
+    }
+}
@@ -0,0 +1,578 @@
+use crate::{
+    calculator::{
+        component::{Calculator, DraggedValue},
+        expression::Expression,
+    },
+    icon_pack,
+    instance::{self, Command, Reply},
+    paths, tabs,
+    window_state::{Frame, WindowState},
+};
+use gpui::{
+    actions, div, impl_actions, point, prelude::*, px, rgb, rgba, size, App, Bounds, Context,
+    DisplayId, Entity, Focusable, KeyBinding, Menu, MenuItem, Pixels, Point, PromptLevel,
+    SharedString, Size, Task, TitlebarOptions, Window, WindowBackgroundAppearance, WindowBounds,
+    WindowHandle, WindowKind, WindowOptions,
+};
+use std::{
+    os::unix::net::UnixListener,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+static NEXT_WINDOW: AtomicUsize = AtomicUsize::new(1);
+
+actions!(
+    workspace,
+    [
+        NewWindow,
+        NewTab,
+        CloseTab,
+        NextTab,
+        PreviousTab,
+        ToggleMiniMode,
+        NextIconPack,
+        Quit
+    ]
+);
+
+#[derive(Clone, Debug, PartialEq, Deserialize, JsonSchema)]
+pub struct ActivateWindow(u64);
+
+impl_actions!(workspace, [ActivateWindow]);
+
+const CONTEXT: &str = "Workspace";
+const WIDTH: f32 = 198.;
+const HEIGHT: f32 = 350.;
+const MINI_HEIGHT: f32 = 270.;
+// The layout scales down to the smallest button size in the settings.
+const MIN_WIDTH: f32 = 160.;
+const MIN_HEIGHT: f32 = 300.;
+const MINI_MIN_HEIGHT: f32 = 230.;
+// Moving or resizing reports new bounds many times a second, so they are saved
+// once the window has settled.
+const SAVE_BOUNDS_DELAY: Duration = Duration::from_millis(500);
+
+pub struct Workspace {
+    title: SharedString,
+    tabs: Vec<Entity<Calculator>>,
+    active: usize,
+    mini: bool,
+    unsaved_bounds: Option<UnsavedBounds>,
+    save_bounds_task: Task<()>,
+}
+
+struct UnsavedBounds {
+    frame: Frame,
+    display: Frame,
+    display_id: Option<String>,
+}
+
+impl Workspace {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let title: SharedString =
+            format!("Calculator {}", NEXT_WINDOW.fetch_add(1, Ordering::SeqCst)).into();
+
+        let calculator = cx.new(|cx| Calculator::new(window, cx));
+
+        Workspace {
+            title,
+            tabs: vec![calculator],
+            active: 0,
+            mini: false,
+            unsaved_bounds: None,
+            save_bounds_task: Task::ready(()),
+        }
+    }
+
+    fn set_mini(&mut self, mini: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.mini = mini;
+
+        for tab in &self.tabs {
+            tab.update(cx, |calculator, cx| calculator.set_mini(mini, cx));
+        }
+        window.focus(&self.tabs[self.active].focus_handle(cx));
+    }
+
+    // Always-on-top is fixed when a window opens, so switching modes moves the
+    // tabs into a new window.
+    fn toggle_mini_mode(
+        &mut self,
+        _: &ToggleMiniMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.save_bounds();
+
+        let mini = !self.mini;
+        let workspace = Workspace {
+            title: self.title.clone(),
+            tabs: self.tabs.clone(),
+            active: self.active,
+            mini,
+            unsaved_bounds: None,
+            save_bounds_task: Task::ready(()),
+        };
+
+        let path = paths::window_state_file();
+        let mut state = WindowState::load(&path).unwrap_or_default();
+        state.mini = mini;
+        save_window_state(&state, &path);
+
+        window.remove_window();
+        cx.defer(move |cx| open(cx, mini, Some(workspace)));
+    }
+
+    fn evaluate(
+        &mut self,
+        expression: &Expression,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<String> {
+        window.activate_window();
+
+        self.tabs[self.active].update(cx, |calculator, cx| calculator.evaluate(expression, cx))
+    }
+
+    fn remember_bounds(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(display) = window.display(cx) else {
+            return;
+        };
+
+        self.unsaved_bounds = Some(UnsavedBounds {
+            frame: frame(window.bounds()),
+            display: frame(display.bounds()),
+            display_id: display.uuid().ok().map(|uuid| uuid.to_string()),
+        });
+
+        // Replacing the task cancels the save scheduled by the previous change.
+        self.save_bounds_task = cx.spawn(|this, mut cx| async move {
+            cx.background_executor().timer(SAVE_BOUNDS_DELAY).await;
+            this.update(&mut cx, |this, _| this.save_bounds()).ok();
+        });
+    }
+
+    fn save_bounds(&mut self) {
+        let Some(bounds) = self.unsaved_bounds.take() else {
+            return;
+        };
+
+        let path = paths::window_state_file();
+        let mut state = WindowState::load(&path).unwrap_or_default();
+        state.remember(self.mini, bounds.frame, bounds.display, bounds.display_id);
+        save_window_state(&state, &path);
+    }
+
+    fn new_tab(&mut self, _: &NewTab, window: &mut Window, cx: &mut Context<Self>) {
+        let calculator = cx.new(|cx| Calculator::new(window, cx));
+        let mini = self.mini;
+        calculator.update(cx, |calculator, cx| calculator.set_mini(mini, cx));
+
+        self.tabs.push(calculator);
+        self.activate_tab(self.tabs.len() - 1, window, cx);
+    }
+
+    fn close_tab(&mut self, _: &CloseTab, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(next) = tabs::after_close(self.active, self.tabs.len()) else {
+            window.remove_window();
+            return;
+        };
+
+        self.tabs.remove(self.active);
+        self.activate_tab(next, window, cx);
+    }
+
+    fn next_tab(&mut self, _: &NextTab, window: &mut Window, cx: &mut Context<Self>) {
+        self.activate_tab(tabs::next(self.active, self.tabs.len()), window, cx);
+    }
+
+    fn previous_tab(&mut self, _: &PreviousTab, window: &mut Window, cx: &mut Context<Self>) {
+        self.activate_tab(tabs::previous(self.active, self.tabs.len()), window, cx);
+    }
+
+    fn activate_tab(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.active = tabs::clamp(index, self.tabs.len());
+        window.focus(&self.tabs[self.active].focus_handle(cx));
+        cx.notify();
+    }
+
+    fn render_tab(&self, index: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_active = index == self.active;
+
+        div()
+            .id(("tab", index))
+            .w(px(18.))
+            .h(px(18.))
+            .flex()
+            .justify_center()
+            .items_center()
+            .rounded_full()
+            .text_xs()
+            .bg(if is_active {
+                rgb(0xff9600)
+            } else {
+                rgb(0x515251)
+            })
+            .child(format!("{}", index + 1))
+            .drag_over::<DraggedValue>(|style, _, _, _| style.bg(rgba(0xffffff60)))
+            .on_drop(cx.listener(move |this, value: &DraggedValue, _, cx| {
+                // A tab closed during the drag leaves nothing to drop onto.
+                if let Some(tab) = this.tabs.get(index) {
+                    tab.update(cx, |calculator, cx| {
+                        calculator.insert_value(value.0.clone(), cx)
+                    });
+                }
+            }))
+            .on_click(cx.listener(move |this, _, window, cx| this.activate_tab(index, window, cx)))
+    }
+}
+
+impl Render for Workspace {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let tabs: Vec<_> = (0..self.tabs.len())
+            .map(|index| self.render_tab(index, cx))
+            .collect();
+
+        div()
+            .relative()
+            .size_full()
+            .key_context(CONTEXT)
+            .on_action(cx.listener(Self::new_tab))
+            .on_action(cx.listener(Self::close_tab))
+            .on_action(cx.listener(Self::next_tab))
+            .on_action(cx.listener(Self::previous_tab))
+            .on_action(cx.listener(Self::toggle_mini_mode))
+            .child(self.tabs[self.active].clone())
+            .when(self.tabs.len() > 1, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top(px(9.))
+                        .right(px(8.))
+                        .flex()
+                        .flex_row()
+                        .gap(px(3.))
+                        .text_color(rgb(0xffffff))
+                        .children(tabs),
+                )
+            })
+    }
+}
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("secondary-n", NewWindow, None),
+        KeyBinding::new("secondary-q", Quit, None),
+        KeyBinding::new("secondary-t", NewTab, Some(CONTEXT)),
+        KeyBinding::new("secondary-w", CloseTab, Some(CONTEXT)),
+        KeyBinding::new("ctrl-tab", NextTab, Some(CONTEXT)),
+        KeyBinding::new("ctrl-shift-tab", PreviousTab, Some(CONTEXT)),
+        KeyBinding::new("secondary-shift-m", ToggleMiniMode, Some(CONTEXT)),
+    ]);
+
+    cx.on_action(|_: &NewWindow, cx| open_window(cx));
+    cx.on_action(|_: &Quit, cx| cx.quit());
+    cx.on_action(|_: &NextIconPack, cx| {
+        if let Err(err) = icon_pack::next_pack(cx) {
+            show_error(&format!("Failed to switch icon packs: {}", err), cx);
+        }
+    });
+    cx.on_action(|action: &ActivateWindow, cx| {
+        let window = cx
+            .windows()
+            .into_iter()
+            .find(|window| window.window_id().as_u64() == action.0);
+
+        if let Some(window) = window {
+            window
+                .update(cx, |_, window, _| window.activate_window())
+                .ok();
+        }
+    });
+}
+
+pub fn open_window(cx: &mut App) {
+    let mini = WindowState::load(&paths::window_state_file())
+        .unwrap_or_default()
+        .mini;
+
+    open(cx, mini, None);
+}
+
+fn open(cx: &mut App, mini: bool, workspace: Option<Workspace>) {
+    let state = WindowState::load(&paths::window_state_file()).unwrap_or_default();
+    let (height, min_height) = match mini {
+        true => (MINI_HEIGHT, MINI_MIN_HEIGHT),
+        false => (HEIGHT, MIN_HEIGHT),
+    };
+    let window_size = size(px(WIDTH), px(height));
+
+    // Fall back to the primary display when the saved one is disconnected.
+    let display = state
+        .display
+        .as_ref()
+        .and_then(|id| {
+            cx.displays()
+                .into_iter()
+                .find(|display| display.uuid().is_ok_and(|uuid| uuid.to_string() == *id))
+        })
+        .or_else(|| cx.primary_display());
+    let display_id = display.as_ref().map(|display| display.id());
+
+    let cascade = px(20. * cx.windows().len() as f32);
+    let bounds = match display
+        .as_ref()
+        .and_then(|display| state.restore(mini, frame(display.bounds())))
+    {
+        Some(frame) => Bounds {
+            origin: point(px(frame.x) + cascade, px(frame.y) + cascade),
+            size: size(px(frame.width), px(frame.height)),
+        },
+        None => default_bounds(cx, display_id, window_size, cascade),
+    };
+
+    let window_options = WindowOptions {
+        window_bounds: Some(WindowBounds::Windowed(bounds)),
+        window_min_size: Some(size(px(MIN_WIDTH), px(min_height))),
+        window_background: WindowBackgroundAppearance::Blurred,
+        titlebar: Some(TitlebarOptions {
+            title: None,
+            traffic_light_position: None,
+            appears_transparent: true,
+        }),
+        app_id: Some("com.dev.hello-gpui".to_string()),
+        kind: if mini {
+            WindowKind::PopUp
+        } else {
+            WindowKind::Normal
+        },
+        display_id,
+        ..Default::default()
+    };
+
+    cx.open_window(window_options, |window, cx| {
+        cx.new(|cx| {
+            let mut workspace = workspace.unwrap_or_else(|| Workspace::new(window, cx));
+            window.set_window_title(&workspace.title);
+            workspace.set_mini(mini, window, cx);
+
+            cx.on_release(|this, cx| {
+                this.save_bounds();
+                cx.defer(refresh_menus);
+            })
+            .detach();
+            cx.on_app_quit(|this, _| {
+                this.save_bounds();
+                async {}
+            })
+            .detach();
+            cx.observe_window_bounds(window, |this, window, cx| this.remember_bounds(window, cx))
+                .detach();
+
+            workspace
+        })
+    })
+    .unwrap();
+
+    refresh_menus(cx);
+}
+
+// Handles commands forwarded by later launches until the app quits.
+pub fn serve(listener: UnixListener, cx: &mut App) {
+    let listener = Arc::new(listener);
+
+    cx.spawn(|cx| async move {
+        loop {
+            let listener = listener.clone();
+            let Ok(request) = cx
+                .background_executor()
+                .spawn(async move { instance::accept(&listener) })
+                .await
+            else {
+                break;
+            };
+
+            let Ok(reply) = cx.update(|cx| run(&request.command, cx)) else {
+                break;
+            };
+            request.reply(reply).ok();
+        }
+    })
+    .detach();
+}
+
+pub fn run(command: &Command, cx: &mut App) -> Reply {
+    cx.activate(true);
+
+    let window = match workspace_window(cx) {
+        Some(window) => window,
+        None => {
+            open_window(cx);
+            workspace_window(cx).ok_or_else(|| "Failed to open a window".to_string())?
+        }
+    };
+
+    match command {
+        Command::Show => window
+            .update(cx, |_, window, _| window.activate_window())
+            .map(|_| String::new())
+            .map_err(|err| err.to_string()),
+        Command::Evaluate(text) => {
+            let expression = Expression::parse(text).map_err(|err| err.to_string())?;
+
+            window
+                .update(cx, |workspace, window, cx| {
+                    workspace.evaluate(&expression, window, cx)
+                })
+                .map_err(|err| err.to_string())?
+                .ok_or_else(|| format!("{} is undefined", text))
+        }
+    }
+}
+
+pub fn active_calculator(cx: &mut App) -> Option<Entity<Calculator>> {
+    let window = workspace_window(cx)?;
+    let workspace = window.read(cx).ok()?;
+
+    Some(workspace.tabs[workspace.active].clone())
+}
+
+// Menu actions have no view to show their errors in, so they are shown as a
+// prompt on the frontmost calculator window.
+fn show_error(message: &str, cx: &mut App) {
+    let shown = workspace_window(cx).and_then(|window| {
+        window
+            .update(cx, |_, window, cx| {
+                let _ = window.prompt(PromptLevel::Warning, message, None, &["OK"], cx);
+            })
+            .ok()
+    });
+
+    if shown.is_none() {
+        eprintln!("{}", message);
+    }
+}
+
+// Window state is saved as windows move and resize, so a failed save is logged
+// rather than interrupting with a prompt.
+fn save_window_state(state: &WindowState, path: &Path) {
+    if let Err(err) = state.save(path) {
+        eprintln!("Failed to save {:?}: {}", path, err);
+    }
+}
+
+fn workspace_window(cx: &mut App) -> Option<WindowHandle<Workspace>> {
+    cx.active_window()
+        .and_then(|window| window.downcast())
+        .or_else(|| {
+            cx.windows()
+                .into_iter()
+                .find_map(|window| window.downcast())
+        })
+}
+
+fn frame(bounds: Bounds<Pixels>) -> Frame {
+    Frame {
+        x: f32::from(bounds.origin.x),
+        y: f32::from(bounds.origin.y),
+        width: f32::from(bounds.size.width),
+        height: f32::from(bounds.size.height),
+    }
+}
+
+#[cfg(not(debug_assertions))]
+fn default_bounds(
+    cx: &mut App,
+    display_id: Option<DisplayId>,
+    size: Size<Pixels>,
+    cascade: Pixels,
+) -> Bounds<Pixels> {
+    let mut bounds = Bounds::centered(display_id, size, cx);
+    bounds.origin.x += cascade;
+    bounds.origin.y += cascade;
+    bounds
+}
+
+#[cfg(debug_assertions)]
+fn default_bounds(
+    cx: &mut App,
+    display_id: Option<DisplayId>,
+    size: Size<Pixels>,
+    cascade: Pixels,
+) -> Bounds<Pixels> {
+    bounds_top_right(
+        cx,
+        display_id,
+        size,
+        Point {
+            x: px(25.) + cascade,
+            y: px(50.) + cascade,
+        },
+    )
+}
+
+fn bounds_top_right(
+    cx: &mut App,
+    display_id: Option<DisplayId>,
+    size: Size<Pixels>,
+    offset: Point<Pixels>,
+) -> Bounds<Pixels> {
+    let display = display_id
+        .and_then(|id| cx.find_display(id))
+        .or_else(|| cx.primary_display());
+
+    display
+        .map(|display| {
+            let origin = display.bounds().top_right();
+            let origin: Point<Pixels> = Point {
+                x: origin.x - offset.x,
+                y: origin.y + offset.y,
+            };
+
+            Bounds::from_corner_and_size(gpui::Corner::TopRight, origin, size)
+        })
+        .unwrap_or_else(|| Bounds {
+            origin: point(px(0.), px(0.)),
+            size,
+        })
+}
+
+pub fn refresh_menus(cx: &mut App) {
+    let windows: Vec<MenuItem> = cx
+        .windows()
+        .into_iter()
+        .filter_map(|window| {
+            let workspace = window.downcast::<Workspace>()?;
+            let title = workspace.read(cx).ok()?.title.clone();
+
+            Some(MenuItem::action(
+                title,
+                ActivateWindow(window.window_id().as_u64()),
+            ))
+        })
+        .collect();
+
+    cx.set_menus(vec![
+        Menu {
+            name: "Calculator".into(),
+            items: vec![
+                MenuItem::action("New Window", NewWindow),
+                MenuItem::action("New Tab", NewTab),
+                MenuItem::action("Close Tab", CloseTab),
+                MenuItem::action("Mini Mode", ToggleMiniMode),
+                MenuItem::separator(),
+                MenuItem::action("Next Icon Pack", NextIconPack),
+                MenuItem::separator(),
+                MenuItem::action("Quit", Quit),
+            ],
+        },
+        Menu {
+            name: "Window".into(),
+            items: windows,
+        },
+    ]);
+}